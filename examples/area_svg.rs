@@ -0,0 +1,40 @@
+use plotlib::page::Page;
+use plotlib::repr::{Area, Plot};
+use plotlib::style::{AreaStyle, LineStyle};
+use plotlib::view::ContinuousView;
+
+fn main() {
+    // A forecast with its confidence band, over the quantity it is based on
+    let days: Vec<f64> = (0..=30).map(f64::from).collect();
+    let usage: Vec<(f64, f64)> = days
+        .iter()
+        .map(|&d| (d, 10. + 3. * (d / 4.).sin()))
+        .collect();
+    let forecast: Vec<(f64, f64)> = days.iter().map(|&d| (d, 14. + d / 5.)).collect();
+    let upper = forecast
+        .iter()
+        .map(|&(d, f)| (d, f + 1. + d / 10.))
+        .collect();
+    let lower = forecast
+        .iter()
+        .map(|&(d, f)| (d, f - 1. - d / 10.))
+        .collect();
+
+    let filled = Area::new(usage)
+        .style(AreaStyle::new().fill("#9ecae1"))
+        .line_style(LineStyle::new().colour("#3182bd").width(1.5))
+        .legend("Usage".to_string());
+    let band = Area::between(upper, lower)
+        .style(AreaStyle::new().fill("#fd8d3c").opacity(0.3))
+        .legend("Forecast range".to_string());
+    let line = Plot::new(forecast).line_style(LineStyle::new().colour("#e6550d"));
+
+    let v = ContinuousView::new()
+        .add(filled)
+        .add(band)
+        .add(line)
+        .x_label("Day")
+        .y_label("Load");
+
+    Page::single(&v).save("area.svg").unwrap();
+}
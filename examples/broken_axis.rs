@@ -0,0 +1,24 @@
+use plotlib::page::Page;
+use plotlib::repr::BarChart;
+use plotlib::style::BoxStyle;
+use plotlib::view::CategoricalView;
+
+fn main() {
+    // Downloads per day of each release, one of which was featured on a news site
+    let releases = [("0.3", 3.1), ("0.4", 4.6), ("0.5", 212.), ("0.5.1", 5.2)];
+    let v = releases
+        .iter()
+        .fold(CategoricalView::new(), |v, &(label, downloads)| {
+            v.add(
+                BarChart::new(downloads)
+                    .label(label)
+                    .style(&BoxStyle::new().fill("steelblue")),
+            )
+        })
+        .y_segments(&[(0., 6.), (208., 214.)])
+        .x_label("Release")
+        .y_label("Downloads per day");
+
+    println!("{}", Page::single(&v).dimensions(60, 20).to_text().unwrap());
+    Page::single(&v).save("broken_axis.svg").unwrap();
+}
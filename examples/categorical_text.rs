@@ -0,0 +1,18 @@
+use plotlib::page::Page;
+use plotlib::repr::{BarChart, BoxPlot};
+use plotlib::view::CategoricalView;
+
+fn main() {
+    let b1 = BarChart::new(5.3).label("control");
+    let b2 = BarChart::new(2.6).label("treated");
+    let b3 = BoxPlot::from_vec(vec![1.2, 2.5, 3.1, 3.3, 4.0, 4.8, 6.2]).label("spread");
+
+    let v = CategoricalView::new()
+        .add(b1)
+        .add(b2)
+        .add(b3)
+        .x_label("Experiment")
+        .y_label("Response");
+
+    println!("{}", Page::single(&v).dimensions(60, 20).to_text().unwrap());
+}
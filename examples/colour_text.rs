@@ -0,0 +1,31 @@
+use plotlib::page::Page;
+use plotlib::repr::Plot;
+use plotlib::style::{LineStyle, PointMarker, PointStyle};
+use plotlib::terminal::Colours;
+use plotlib::view::ContinuousView;
+
+fn main() {
+    let sine: Vec<(f64, f64)> = (0..=100)
+        .map(|i| {
+            let x = f64::from(i) / 10.;
+            (x, x.sin())
+        })
+        .collect();
+    let cosine: Vec<(f64, f64)> = sine.iter().map(|&(x, _)| (x, x.cos())).collect();
+
+    let s1 = Plot::new(sine).line_style(LineStyle::new().colour("#DD3355"));
+    let s2 = Plot::new(cosine).line_style(LineStyle::new().colour("steelblue"));
+    let s3 = Plot::new(vec![(2., 0.5), (5., -0.5), (8., 0.)]).point_style(
+        PointStyle::new()
+            .marker(PointMarker::Square)
+            .colour("#35C788"),
+    );
+    let v = ContinuousView::new().add(s1).add(s2).add(s3);
+
+    // Falls back to plain text when NO_COLOR is set or the output isn't a terminal
+    let page = Page::single(&v)
+        .dimensions(80, 20)
+        .text_colours(Colours::detect());
+    println!("{}", page.to_text().unwrap());
+    println!("{}", page.to_braille().unwrap());
+}
@@ -0,0 +1,44 @@
+use plotlib::page::{Cell, Page};
+use plotlib::repr::{BarChart, Histogram, HistogramBins, Plot};
+use plotlib::style::{BoxStyle, LineStyle, PointMarker, PointStyle};
+use plotlib::view::{CategoricalView, ContinuousView};
+
+fn main() {
+    let trend = Plot::new(vec![(0., 1.), (1., 2.5), (2., 2.1), (3., 3.4), (4., 3.9)])
+        .line_style(LineStyle::new().colour("burlywood"));
+    let wide = ContinuousView::new()
+        .add(trend)
+        .x_label("Day")
+        .y_label("Requests");
+
+    let scatter = Plot::new(vec![(0.3, 1.2), (1.4, 0.8), (2.2, 2.6), (3.1, 1.9)]).point_style(
+        PointStyle::new()
+            .marker(PointMarker::Circle)
+            .colour("#DD3355"),
+    );
+    let points = ContinuousView::new().add(scatter).x_label("Load");
+
+    let bars = CategoricalView::new()
+        .add(BarChart::new(5.3).label("a"))
+        .add(
+            BarChart::new(2.6)
+                .label("b")
+                .style(&BoxStyle::new().fill("darkolivegreen")),
+        );
+
+    let data = [0.3, 0.5, 6.4, 5.3, 3.6, 3.6, 3.5, 7.5, 4.0];
+    let histogram =
+        ContinuousView::new().add(Histogram::from_slice(&data, HistogramBins::Count(5)));
+
+    // A 2x3 grid where the line plot spans the whole top row
+    Page::empty()
+        .dimensions(1200, 800)
+        .layout(2, 3)
+        .gap(20)
+        .add_plot_at(&wide, Cell::new(0, 0).span(1, 3))
+        .add_plot(&points)
+        .add_plot(&bars)
+        .add_plot(&histogram)
+        .save("dashboard.svg")
+        .unwrap();
+}
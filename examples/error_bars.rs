@@ -0,0 +1,31 @@
+use plotlib::page::Page;
+use plotlib::repr::Plot;
+use plotlib::style::{ErrorBarStyle, LineStyle, PointStyle};
+use plotlib::view::ContinuousView;
+
+fn main() {
+    // Repeated measurements of a decay, with the spread of each as its error
+    let data = vec![
+        (1., 8.2),
+        (2., 5.9),
+        (3., 4.4),
+        (4., 3.0),
+        (5., 2.3),
+        (6., 1.5),
+    ];
+    let measured = Plot::new(data)
+        .y_errors(vec![0.9, 0.6, 0.7, 0.4, 0.5, 0.3])
+        .x_errors_asymmetric(vec![(0.1, 0.3); 6])
+        .point_style(PointStyle::new().colour("#1f77b4"))
+        .line_style(LineStyle::new().colour("#1f77b4").width(1.))
+        .error_style(ErrorBarStyle::new().colour("#555555").cap_width(8.))
+        .legend("Measured".to_string());
+
+    let v = ContinuousView::new()
+        .add(measured)
+        .x_label("Time (h)")
+        .y_label("Activity (kBq)");
+
+    Page::single(&v).save("error_bars.svg").unwrap();
+    println!("{}", Page::single(&v).dimensions(80, 24).to_text().unwrap());
+}
@@ -0,0 +1,30 @@
+use plotlib::page::Page;
+use plotlib::repr::{Histogram, HistogramBins, Plot};
+use plotlib::style::{LineStyle, PointStyle};
+use plotlib::view::ContinuousView;
+
+fn main() {
+    let data: Vec<(f64, f64)> = (0..=200)
+        .map(|i| {
+            let x = f64::from(i) / 20.;
+            (x, x.sin())
+        })
+        .collect();
+    let line = Plot::new(data).line_style(LineStyle::new());
+    let points = Plot::new(vec![(1., 0.5), (4., -0.2), (8., 0.9)]).point_style(PointStyle::new());
+    let v = ContinuousView::new()
+        .add(line)
+        .add(points)
+        .x_label("Time (s)")
+        .y_label("Amplitude");
+
+    let samples = [0.3, 0.5, 6.4, 5.3, 3.6, 3.6, 3.5, 7.5, 4.0, 4.2, 4.4, 5.1];
+    let h = ContinuousView::new().add(Histogram::from_slice(&samples, HistogramBins::Count(6)));
+
+    let page = Page::empty()
+        .dimensions(60, 15)
+        .gap(4)
+        .add_plot(&v)
+        .add_plot(&h);
+    println!("{}", page.to_braille().unwrap());
+}
@@ -0,0 +1,21 @@
+use plotlib::page::Page;
+use plotlib::repr::Plot;
+use plotlib::style::LineStyle;
+use plotlib::view::ContinuousView;
+
+fn main() {
+    let data: Vec<(f64, f64)> = (0..=40)
+        .map(|i| {
+            let x = f64::from(i) / 4.;
+            (x, x.sin())
+        })
+        .collect();
+    let l1 = Plot::new(data).line_style(LineStyle::new());
+
+    let v = ContinuousView::new()
+        .add(l1)
+        .x_label("Time (s)")
+        .y_label("Amplitude");
+
+    println!("{}", Page::single(&v).dimensions(80, 20).to_text().unwrap());
+}
@@ -0,0 +1,33 @@
+use plotlib::page::Page;
+use plotlib::repr::Plot;
+use plotlib::scale::Scale;
+use plotlib::style::{LineStyle, PointStyle};
+use plotlib::view::ContinuousView;
+
+fn main() {
+    // Request latency in milliseconds at each load in requests per second
+    let data = vec![
+        (10., 0.8),
+        (30., 1.1),
+        (100., 1.9),
+        (300., 4.2),
+        (1000., 16.),
+        (3000., 95.),
+        (10000., 1400.),
+    ];
+    let p = Plot::new(data)
+        .line_style(LineStyle::new())
+        .point_style(PointStyle::new());
+
+    let v = ContinuousView::new()
+        .add(p)
+        .x_range(5., 20000.)
+        .y_range(0.5, 2000.)
+        .x_scale(Scale::Log10)
+        .y_scale(Scale::Log10)
+        .x_label("Load (req/s)")
+        .y_label("Latency (ms)");
+
+    println!("{}", Page::single(&v).dimensions(80, 20).to_text().unwrap());
+    Page::single(&v).save("log_scale.svg").unwrap();
+}
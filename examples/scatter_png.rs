@@ -0,0 +1,43 @@
+use plotlib::grid::Grid;
+use plotlib::page::Page;
+use plotlib::repr::Plot;
+use plotlib::style::{LineStyle, PointMarker, PointStyle};
+use plotlib::view::{ContinuousView, View};
+
+fn main() {
+    let data1 = vec![
+        (-3.0, 2.3),
+        (-1.6, 5.3),
+        (0.3, 0.7),
+        (4.3, -1.4),
+        (6.4, 4.3),
+        (8.5, 3.7),
+    ];
+    let s1: Plot = Plot::new(data1)
+        .point_style(
+            PointStyle::new()
+                .marker(PointMarker::Square)
+                .colour("#DD3355"),
+        )
+        .line_style(LineStyle::new().colour("#DD3355").width(1.))
+        .legend("Measured".to_string());
+
+    let data2 = vec![(-1.4, 2.5), (7.2, -0.3)];
+    let s2: Plot = Plot::new(data2).point_style(
+        PointStyle::new()
+            .marker(PointMarker::Cross)
+            .colour("#35C788"),
+    );
+
+    let mut v = ContinuousView::new()
+        .add(s1)
+        .add(s2)
+        .x_range(-5., 10.)
+        .y_range(-2., 6.)
+        .x_label("Some varying variable")
+        .y_label("The response of something");
+    v.add_grid(Grid::new(3, 4));
+
+    // The file extension selects the PNG backend
+    Page::single(&v).save("scatter_with_grid.png").unwrap();
+}
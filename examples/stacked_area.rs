@@ -0,0 +1,43 @@
+use plotlib::page::Page;
+use plotlib::repr::StackedArea;
+use plotlib::style::{AreaStyle, LineStyle};
+use plotlib::view::ContinuousView;
+
+fn main() {
+    // Memory used by each service over a day, sampled every hour
+    let hours: Vec<f64> = (0..24).map(f64::from).collect();
+    let load = |base: f64, swing: f64, peak: f64| -> Vec<f64> {
+        hours
+            .iter()
+            .map(|&h| base + swing * (-(h - peak) * (h - peak) / 18.).exp())
+            .collect()
+    };
+
+    let usage = StackedArea::new(hours.clone())
+        .add_layer("api", load(1.2, 2.5, 14.))
+        .add_layer("database", load(3.0, 1.0, 15.))
+        .add_layer("cache", load(0.8, 0.6, 9.))
+        .add_styled_layer("batch", load(0.2, 2.0, 3.), AreaStyle::new().opacity(0.7))
+        .line_style(LineStyle::new().colour("white").width(1.));
+
+    let v = ContinuousView::new()
+        .add(usage)
+        .x_range(0., 23.)
+        .x_label("Hour")
+        .y_label("Memory (GB)");
+    Page::single(&v).save("stacked_area.svg").unwrap();
+
+    // The same services as a share of the total
+    let share = StackedArea::new(hours.clone())
+        .add_layer("api", load(1.2, 2.5, 14.))
+        .add_layer("database", load(3.0, 1.0, 15.))
+        .add_layer("cache", load(0.8, 0.6, 9.))
+        .add_layer("batch", load(0.2, 2.0, 3.))
+        .normalised();
+    let v = ContinuousView::new()
+        .add(share)
+        .x_range(0., 23.)
+        .x_label("Hour")
+        .y_label("Share of memory (%)");
+    println!("{}", Page::single(&v).dimensions(80, 24).to_text().unwrap());
+}
@@ -0,0 +1,27 @@
+use plotlib::format::TickFormat;
+use plotlib::page::Page;
+use plotlib::repr::Plot;
+use plotlib::style::LineStyle;
+use plotlib::view::ContinuousView;
+
+fn main() {
+    // Monthly revenue against the number of active users
+    let data = vec![
+        (120_000., 18_500.),
+        (340_000., 41_250.),
+        (610_000., 60_100.),
+        (1_200_000., 98_000.),
+        (2_050_000., 143_700.),
+    ];
+    let p = Plot::new(data).line_style(LineStyle::new());
+
+    let v = ContinuousView::new()
+        .add(p)
+        .x_tick_format(TickFormat::SI(1))
+        .y_tick_format(TickFormat::Currency("$".into(), 0))
+        .x_label("Active users")
+        .y_label("Revenue");
+
+    println!("{}", Page::single(&v).dimensions(70, 15).to_text().unwrap());
+    Page::single(&v).save("tick_format.svg").unwrap();
+}
@@ -0,0 +1,29 @@
+use plotlib::page::Page;
+use plotlib::repr::Plot;
+use plotlib::style::LineStyle;
+use plotlib::time::timestamp;
+use plotlib::view::ContinuousView;
+
+fn main() {
+    // Hourly temperature readings over three days
+    let start = timestamp(2020, 3, 14, 0, 0, 0);
+    let data: Vec<(f64, f64)> = (0..72)
+        .map(|hour| {
+            let t = f64::from(hour);
+            let daily = (std::f64::consts::PI * (t - 9.) / 12.).sin();
+            (start + t * 3600., 12. + 6. * daily)
+        })
+        .collect();
+    let p = Plot::new(data).line_style(LineStyle::new());
+
+    let v = ContinuousView::new()
+        .add(p)
+        .x_time()
+        .x_label("Time (UTC)")
+        .y_label("Temperature (°C)");
+
+    println!("{}", Page::single(&v).dimensions(80, 15).to_text().unwrap());
+
+    let v = v.x_time_format("%a %H:%M");
+    Page::single(&v).save("time_series.svg").unwrap();
+}
@@ -4,6 +4,12 @@ A module for managing axes
 
 */
 
+use crate::format::TickFormat;
+use crate::scale::Scale;
+use crate::ticks::{self, TickAlgorithm};
+use crate::time;
+use crate::utils::PairWise;
+
 #[derive(Debug, Clone)]
 pub struct Range {
     pub lower: f64,
@@ -23,17 +29,83 @@ impl Range {
 #[derive(Debug)]
 pub struct ContinuousAxis {
     range: Range,
+    scale: Scale,
     ticks: Vec<f64>,
+    minor_ticks: Vec<f64>,
+    format: TickFormat,
+    tick_labels: Vec<(f64, String)>,
     label: String,
+    reversed: bool,
 }
 
 impl ContinuousAxis {
-    /// Constructs a new ContinuousAxis
+    /// Constructs a new linear ContinuousAxis
     pub fn new(lower: f64, upper: f64, max_ticks: usize) -> ContinuousAxis {
+        ContinuousAxis::scaled(lower, upper, max_ticks, Scale::Linear)
+    }
+
+    /// Constructs a new ContinuousAxis with ticks chosen to suit the scale
+    pub fn scaled(lower: f64, upper: f64, max_ticks: usize, scale: Scale) -> ContinuousAxis {
+        let (ticks, minor_ticks) = calculate_scaled_ticks(lower, upper, max_ticks, scale);
         ContinuousAxis {
             range: Range::new(lower, upper),
-            ticks: calculate_ticks(lower, upper, max_ticks),
+            scale,
+            ticks,
+            minor_ticks,
+            format: TickFormat::Plain,
+            tick_labels: vec![],
             label: "".into(),
+            reversed: false,
+        }
+    }
+
+    /// Constructs a new ContinuousAxis with ticks placed by the given algorithm
+    ///
+    /// The extended algorithm is only used for linear and power scales,
+    /// and may widen the range to start and end on a tick.
+    pub fn with_algorithm(
+        lower: f64,
+        upper: f64,
+        max_ticks: usize,
+        scale: Scale,
+        algorithm: TickAlgorithm,
+    ) -> ContinuousAxis {
+        let extended = match (algorithm, scale) {
+            (TickAlgorithm::Extended { expand }, Scale::Linear)
+            | (TickAlgorithm::Extended { expand }, Scale::Power(_)) => {
+                ticks::calculate_extended_ticks(lower, upper, max_ticks, expand)
+            }
+            _ => None,
+        };
+        match extended {
+            Some((lower, upper, ticks)) => ContinuousAxis {
+                range: Range::new(lower, upper),
+                scale,
+                ticks,
+                minor_ticks: vec![],
+                format: TickFormat::Plain,
+                tick_labels: vec![],
+                label: "".into(),
+                reversed: false,
+            },
+            None => ContinuousAxis::scaled(lower, upper, max_ticks, scale),
+        }
+    }
+
+    /// Constructs a new ContinuousAxis of timestamps with calendar-aligned ticks
+    ///
+    /// The ticks are labelled with a pattern suiting their spacing.
+    pub fn time(lower: f64, upper: f64, max_ticks: usize) -> ContinuousAxis {
+        let (ticks, format) = time::calculate_time_ticks(lower, upper, max_ticks);
+        ContinuousAxis {
+            range: Range::new(lower, upper),
+            scale: Scale::Linear,
+            ticks,
+            minor_ticks: vec![],
+            format: TickFormat::Time(format),
+            tick_labels: vec![],
+            label: "".into(),
+            reversed: false,
         }
     }
 
@@ -61,6 +133,154 @@ impl ContinuousAxis {
     pub fn ticks(&self) -> &Vec<f64> {
         &self.ticks
     }
+
+    /// Run the axis from the maximum to the minimum, so that it grows downward or leftward
+    pub fn reverse(mut self) -> Self {
+        self.reversed = !self.reversed;
+        self
+    }
+
+    /// Whether the axis runs from the maximum to the minimum
+    pub fn is_reversed(&self) -> bool {
+        self.reversed
+    }
+
+    /// Set how the ticks are labelled
+    pub fn format(mut self, format: TickFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Replace the automatic ticks with the given ones, dropping any outside the range
+    ///
+    /// Ticks given a label are labelled with it rather than with their formatted value.
+    pub fn explicit_ticks(mut self, ticks: &[(f64, Option<String>)]) -> Self {
+        let (lower, upper) = (self.min(), self.max());
+        let in_range = ticks.iter().filter(|(t, _)| lower <= *t && *t <= upper);
+        self.ticks = in_range.clone().map(|&(t, _)| t).collect();
+        self.ticks
+            .sort_by(|a, b| a.partial_cmp(b).expect("ticks in range are never NaN"));
+        self.tick_labels = in_range
+            .filter_map(|(t, label)| label.clone().map(|label| (*t, label)))
+            .collect();
+        self.minor_ticks = vec![];
+        self
+    }
+
+    /// Replace the minor ticks by dividing the gap between each pair of major ticks into `parts`
+    ///
+    /// The gaps before the first and after the last major tick are divided as if there were
+    /// another major tick one gap beyond them. Fewer than two parts gives no minor ticks.
+    pub fn subdivide(mut self, parts: u32) -> Self {
+        self.minor_ticks = vec![];
+        if parts < 2 || self.ticks.len() < 2 {
+            return self;
+        }
+        // Extend the major ticks by a gap at either end, measured along the axis
+        let position = |t: f64| self.scale.transform(t);
+        let (first, second) = (self.ticks[0], self.ticks[1]);
+        let (last, penultimate) = (
+            self.ticks[self.ticks.len() - 1],
+            self.ticks[self.ticks.len() - 2],
+        );
+        let before = self.scale.inverse(2. * position(first) - position(second));
+        let after = self
+            .scale
+            .inverse(2. * position(last) - position(penultimate));
+        let majors: Vec<f64> = std::iter::once(before)
+            .chain(self.ticks.iter().cloned())
+            .chain(std::iter::once(after))
+            .collect();
+
+        let (lower, upper) = (self.min(), self.max());
+        for (&start, &end) in majors.pairwise() {
+            let step = (end - start) / f64::from(parts);
+            self.minor_ticks.extend(
+                (1..parts)
+                    .map(|i| start + f64::from(i) * step)
+                    .filter(|&t| lower <= t && t <= upper),
+            );
+        }
+        self
+    }
+
+    /// The text to label a tick with
+    pub fn tick_label(&self, tick: f64) -> String {
+        match self.tick_labels.iter().find(|&&(t, _)| t == tick) {
+            Some((_, label)) => label.clone(),
+            None => self.format.format(tick),
+        }
+    }
+
+    /// Get the positions of the unlabelled ticks between the major ones
+    pub fn minor_ticks(&self) -> &Vec<f64> {
+        &self.minor_ticks
+    }
+
+    pub fn get_scale(&self) -> Scale {
+        self.scale
+    }
+
+    /// How far along the axis a value lies, from 0 at the minimum to 1 at the maximum,
+    /// or from 0 at the maximum to 1 at the minimum if the axis is reversed
+    ///
+    /// Values outside the domain of the scale, such as zero on a logarithmic axis,
+    /// are placed at the minimum.
+    pub fn fraction(&self, value: f64) -> f64 {
+        let lower = self.scale.transform(self.min());
+        let upper = self.scale.transform(self.max());
+        let position = self.scale.transform(value);
+        let fraction = if position.is_infinite() || (position.is_nan() && !value.is_nan()) {
+            0.
+        } else {
+            (position - lower) / (upper - lower)
+        };
+        if self.reversed {
+            1. - fraction
+        } else {
+            fraction
+        }
+    }
+
+    /// The value which lies the given fraction of the way along the axis
+    pub fn value_at(&self, fraction: f64) -> f64 {
+        let lower = self.scale.transform(self.min());
+        let upper = self.scale.transform(self.max());
+        let fraction = if self.reversed {
+            1. - fraction
+        } else {
+            fraction
+        };
+        self.scale.inverse(lower + fraction * (upper - lower))
+    }
+}
+
+/// Lay out the segments of a broken axis along a face `length` long, leaving `gap` between each
+///
+/// Each segment is given as its offset from the start of the face and its size,
+/// which is in proportion to its length along the axis.
+/// The segments run from the end of the face if the axes are reversed.
+pub(crate) fn layout_segments(axes: &[ContinuousAxis], length: f64, gap: f64) -> Vec<(f64, f64)> {
+    let spans: Vec<f64> = axes
+        .iter()
+        .map(|a| a.scale.transform(a.max()) - a.scale.transform(a.min()))
+        .collect();
+    let total: f64 = spans.iter().sum();
+    let available = length - gap * (axes.len() as f64 - 1.);
+    let mut offset = 0.;
+    axes.iter()
+        .zip(spans)
+        .map(|(a, span)| {
+            let size = available * span / total;
+            let start = if a.is_reversed() {
+                length - offset - size
+            } else {
+                offset
+            };
+            offset += size + gap;
+            (start, size)
+        })
+        .collect()
 }
 
 #[derive(Debug)]
@@ -121,7 +341,7 @@ impl TickSteps {
         let base_step_scale = 10f64.powf(power);
         BASE_STEPS
             .iter()
-            .map(|&s| (f64::from(s) * base_step_scale))
+            .map(|&s| f64::from(s) * base_step_scale)
             .collect()
     }
 }
@@ -151,7 +371,7 @@ fn generate_ticks(min: f64, max: f64, step_size: f64) -> Vec<f64> {
             // standard spanning axis
             ticks.extend(
                 (1..)
-                    .map(|n| -1.0 * fix(f64::from(n) * step_size))
+                    .map(|n| -fix(f64::from(n) * step_size))
                     .take_while(|&v| v >= min)
                     .collect::<Vec<f64>>()
                     .iter()
@@ -167,7 +387,7 @@ fn generate_ticks(min: f64, max: f64, step_size: f64) -> Vec<f64> {
             // entirely negative axis
             ticks.extend(
                 (0..)
-                    .map(|n| -1.0 * fix((f64::from(n) * step_size) - max))
+                    .map(|n| -fix((f64::from(n) * step_size) - max))
                     .take_while(|&v| v >= min)
                     .collect::<Vec<f64>>()
                     .iter()
@@ -191,7 +411,7 @@ fn number_of_ticks(min: f64, max: f64, step_size: f64) -> usize {
 }
 
 /// Given a range of values, and a maximum number of ticks, calulate the step between the ticks
-fn calculate_tick_step_for_range(min: f64, max: f64, max_ticks: usize) -> f64 {
+pub(crate) fn calculate_tick_step_for_range(min: f64, max: f64, max_ticks: usize) -> f64 {
     let range = max - min;
     let min_tick_step = range / max_ticks as f64;
     // Get the first entry which is our smallest possible tick step size
@@ -215,6 +435,162 @@ fn calculate_ticks(min: f64, max: f64, max_ticks: usize) -> Vec<f64> {
     generate_ticks(min, max, tick_step)
 }
 
+/// Parse `mantissa`e`exponent`, which gives the closest float to the decimal value
+fn decimal(mantissa: i32, exponent: i32) -> f64 {
+    format!("{}e{}", mantissa, exponent)
+        .parse()
+        .expect("ERROR: Could not parse tick value")
+}
+
+/// `mantissa` times _e_ to the power `exponent`, to four significant figures to give readable labels
+pub(crate) fn natural(mantissa: i32, exponent: i32) -> f64 {
+    format!(
+        "{:.3e}",
+        f64::from(mantissa) * std::f64::consts::E.powi(exponent)
+    )
+    .parse()
+    .expect("ERROR: Could not parse tick value")
+}
+
+/// Ticks at powers of ten between `lower` and `upper`, with minor ticks between them,
+/// or `None` if fewer than two powers fit in the range
+///
+/// If there are more than `max_ticks` powers then only every nth one is labelled
+/// and the rest become minor ticks.
+fn calculate_decade_ticks(
+    lower: f64,
+    upper: f64,
+    max_ticks: usize,
+) -> Option<(Vec<f64>, Vec<f64>)> {
+    if lower <= 0. || lower >= upper {
+        return None;
+    }
+    let first = lower.log10().ceil() as i32;
+    let last = upper.log10().floor() as i32;
+    if last <= first {
+        return None;
+    }
+    let count = (last - first + 1) as usize;
+    let step = count.div_ceil(max_ticks.max(1));
+
+    let mut ticks = vec![];
+    let mut minor_ticks = vec![];
+    for exponent in (first - 1)..=last {
+        let power = decimal(1, exponent);
+        if power >= lower {
+            if ((exponent - first) as usize).is_multiple_of(step) {
+                ticks.push(power);
+            } else {
+                minor_ticks.push(power);
+            }
+        }
+        if step == 1 {
+            minor_ticks.extend(
+                (2..10)
+                    .map(|m| decimal(m, exponent))
+                    .filter(|&v| lower <= v && v <= upper),
+            );
+        }
+    }
+    Some((ticks, minor_ticks))
+}
+
+/// Ticks at powers of _e_ between `lower` and `upper`, with minor ticks between them,
+/// or `None` if fewer than two powers fit in the range
+///
+/// Like `calculate_decade_ticks`, only every nth power is labelled if there are too many.
+fn calculate_natural_ticks(
+    lower: f64,
+    upper: f64,
+    max_ticks: usize,
+) -> Option<(Vec<f64>, Vec<f64>)> {
+    if lower <= 0. || lower >= upper {
+        return None;
+    }
+    let first = lower.ln().ceil() as i32;
+    let last = upper.ln().floor() as i32;
+    if last <= first {
+        return None;
+    }
+    let count = (last - first + 1) as usize;
+    let step = count.div_ceil(max_ticks.max(1));
+
+    let mut ticks = vec![];
+    let mut minor_ticks = vec![];
+    for exponent in (first - 1)..=last {
+        let power = natural(1, exponent);
+        if exponent >= first {
+            if ((exponent - first) as usize).is_multiple_of(step) {
+                ticks.push(power);
+            } else {
+                minor_ticks.push(power);
+            }
+        }
+        if step == 1 {
+            minor_ticks.extend(
+                (2..)
+                    .take_while(|&m| f64::from(m) < std::f64::consts::E)
+                    .map(|m| natural(m, exponent))
+                    .filter(|&v| lower <= v && v <= upper),
+            );
+        }
+    }
+    Some((ticks, minor_ticks))
+}
+
+/// Given an axis range and its scale, calculate the major and minor ticks
+fn calculate_scaled_ticks(
+    lower: f64,
+    upper: f64,
+    max_ticks: usize,
+    scale: Scale,
+) -> (Vec<f64>, Vec<f64>) {
+    let linear = || (calculate_ticks(lower, upper, max_ticks), vec![]);
+    match scale {
+        Scale::Linear | Scale::Power(_) => linear(),
+        Scale::Log10 => calculate_decade_ticks(lower, upper, max_ticks).unwrap_or_else(linear),
+        Scale::Ln => calculate_natural_ticks(lower, upper, max_ticks).unwrap_or_else(linear),
+        Scale::SymLog(threshold) => {
+            // Decades are only marked outside the linear region around zero
+            let threshold = decimal(1, threshold.log10().ceil() as i32);
+            // The ticks are shared between the two sides by how many decades each covers,
+            // after one for zero
+            let decades = |lower: f64, upper: f64| {
+                (upper.log10().floor() - lower.log10().ceil() + 1.).max(0.) as usize
+            };
+            let positive_decades = decades(threshold.max(lower), upper);
+            let negative_decades = decades(threshold.max(-upper), -lower);
+            let zero = (lower <= 0. && 0. <= upper) as usize;
+            let budget = max_ticks.saturating_sub(zero);
+            let positive_ticks = if negative_decades == 0 {
+                budget
+            } else if positive_decades == 0 {
+                0
+            } else {
+                let share = budget as f64 * positive_decades as f64
+                    / (positive_decades + negative_decades) as f64;
+                (share.round() as usize).clamp(1, budget.saturating_sub(1).max(1))
+            };
+            let negative_ticks = budget.saturating_sub(positive_ticks);
+            let positive = calculate_decade_ticks(threshold.max(lower), upper, positive_ticks);
+            let negative = calculate_decade_ticks(threshold.max(-upper), -lower, negative_ticks);
+            if positive.is_none() && negative.is_none() {
+                return linear();
+            }
+            let (mut ticks, mut minor_ticks) = negative.unwrap_or_default();
+            ticks = ticks.iter().rev().map(|t| -t).collect();
+            minor_ticks = minor_ticks.iter().rev().map(|t| -t).collect();
+            if lower <= 0. && 0. <= upper {
+                ticks.push(0.);
+            }
+            let (positive_ticks, positive_minor_ticks) = positive.unwrap_or_default();
+            ticks.extend(positive_ticks);
+            minor_ticks.extend(positive_minor_ticks);
+            (ticks, minor_ticks)
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -262,6 +638,106 @@ mod tests {
         assert_eq!(calculate_tick_step_for_range(0.0, 0.06, 6), 0.02);
     }
 
+    #[test]
+    fn test_scaled_ticks() {
+        let axis = ContinuousAxis::scaled(5., 2000., 6, Scale::Log10);
+        assert_eq!(axis.ticks(), &[10., 100., 1000.]);
+        assert_eq!(axis.minor_ticks()[..3], [5., 6., 7.]);
+        assert_eq!(axis.minor_ticks().len(), 5 + 8 + 8 + 1);
+
+        // Too many decades to label them all
+        let axis = ContinuousAxis::scaled(1e-3, 1e6, 4, Scale::Log10);
+        assert_eq!(axis.ticks(), &[0.001, 1., 1000., 1000000.]);
+        assert_eq!(axis.minor_ticks(), &[0.01, 0.1, 10., 100., 10000., 100000.]);
+
+        // Less than a decade falls back to linear ticks
+        let axis = ContinuousAxis::scaled(2., 8., 6, Scale::Log10);
+        assert_eq!(axis.ticks(), &[2., 4., 6., 8.]);
+        assert!(axis.minor_ticks().is_empty());
+
+        let axis = ContinuousAxis::scaled(0.5, 25., 6, Scale::Ln);
+        let labels: Vec<_> = axis.ticks().iter().map(|t| t.to_string()).collect();
+        assert_eq!(labels, ["1", "2.718", "7.389", "20.09"]);
+        assert_eq!(axis.minor_ticks(), &[0.7358, 2., 5.437, 14.78]);
+
+        // Too many powers of e to label them all
+        let axis = ContinuousAxis::scaled(1., 1e4, 4, Scale::Ln);
+        assert_eq!(axis.ticks(), &[1., 20.09, 403.4, 8103.]);
+        let minor: Vec<_> = axis.minor_ticks().iter().map(|t| t.to_string()).collect();
+        assert_eq!(minor, ["2.718", "7.389", "54.6", "148.4", "1097", "2981"]);
+
+        let axis = ContinuousAxis::scaled(-500., 50., 6, Scale::SymLog(1.));
+        assert_eq!(axis.ticks(), &[-100., -10., -1., 0., 1., 10.]);
+        assert!(axis.minor_ticks().contains(&-500.));
+        assert!(axis.minor_ticks().contains(&50.));
+
+        // The sides share the ticks rather than each having as many as are allowed
+        for &(lower, upper) in &[(-1e6, 1e6), (-1e9, 1e3), (-5., 1e12)] {
+            let axis = ContinuousAxis::scaled(lower, upper, 6, Scale::SymLog(1.));
+            assert!(axis.ticks().len() <= 6, "{:?}", axis.ticks());
+            assert!(axis.ticks().contains(&0.));
+        }
+        let axis = ContinuousAxis::scaled(-1e6, 1e6, 6, Scale::SymLog(1.));
+        assert_eq!(axis.ticks(), &[-10000., -1., 0., 1., 1000., 1000000.]);
+    }
+
+    #[test]
+    fn test_subdivide() {
+        let axis = ContinuousAxis::new(-1., 5.5, 6).subdivide(2);
+        assert_eq!(axis.ticks(), &[0., 2., 4.]);
+        assert_eq!(axis.minor_ticks(), &[-1., 1., 3., 5.]);
+        assert!(ContinuousAxis::new(0., 5., 6)
+            .subdivide(1)
+            .minor_ticks()
+            .is_empty());
+
+        let axis = ContinuousAxis::scaled(2., 1000., 6, Scale::Log10).subdivide(3);
+        assert_eq!(axis.ticks(), &[10., 100., 1000.]);
+        assert_eq!(axis.minor_ticks(), &[4., 7., 40., 70., 400., 700.]);
+
+        // A log scale can have its default minor ticks turned off
+        let axis = ContinuousAxis::scaled(2., 1000., 6, Scale::Log10).subdivide(0);
+        assert!(axis.minor_ticks().is_empty());
+    }
+
+    #[test]
+    fn test_fraction() {
+        let axis = ContinuousAxis::scaled(1., 1000., 6, Scale::Log10);
+        assert_eq!(axis.fraction(1.), 0.);
+        assert!((axis.fraction(10.) - 1. / 3.).abs() < 1e-12);
+        assert_eq!(axis.fraction(1000.), 1.);
+        assert_eq!(axis.fraction(0.), 0.);
+        assert!((axis.value_at(2. / 3.) - 100.).abs() < 1e-9);
+
+        let axis = ContinuousAxis::new(-2., 5., 6);
+        assert_eq!(axis.fraction(0.), 2. / 7.);
+        assert_eq!(axis.value_at(1.), 5.);
+
+        let axis = axis.reverse();
+        assert_eq!(axis.fraction(5.), 0.);
+        assert_eq!(axis.fraction(-2.), 1.);
+        assert_eq!(axis.value_at(1.), -2.);
+    }
+
+    #[test]
+    fn test_layout_segments() {
+        let axes = vec![
+            ContinuousAxis::new(0., 10., 6),
+            ContinuousAxis::new(90., 120., 6),
+        ];
+        assert_eq!(
+            layout_segments(&axes, 50., 10.),
+            vec![(0., 10.), (20., 30.)]
+        );
+        assert_eq!(layout_segments(&axes[..1], 50., 10.), vec![(0., 50.)]);
+
+        let axes: Vec<_> = axes.into_iter().map(|a| a.reverse()).collect();
+        assert_eq!(
+            layout_segments(&axes, 50., 10.),
+            vec![(40., 10.), (0., 30.)]
+        );
+    }
+
     #[test]
     fn test_calculate_ticks() {
         macro_rules! assert_approx_eq {
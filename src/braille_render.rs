@@ -0,0 +1,356 @@
+/*!
+A module for plotting graphs with Unicode braille patterns.
+
+Each character cell holds a block of 2×4 dots,
+so the face has eight times as many points as in `text_render`.
+The axes and labels are still drawn by `text_render` around the face.
+*/
+
+use crate::axis;
+use crate::repr;
+use crate::style;
+use crate::text_render::{bresenham, clip_segment, interpolate};
+use crate::utils::PairWise;
+
+/// The bit of a braille pattern for each dot, indexed by row from the top and then by column
+const DOT_BITS: [[u32; 2]; 4] = [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+
+/**
+A face of braille cells which individual dots can be set on
+
+Dots are counted from the corner where the axes meet, like the cell offsets of `text_render`,
+so the first character of the face covers the third and fourth columns of dots
+and the bottom row of dots sits on the x-axis and is never drawn.
+*/
+struct Canvas {
+    /// The number of dots across, not counting those on the y-axis
+    width: u32,
+    /// The number of dots up, not counting the one on the x-axis
+    height: u32,
+    /// The bits of the pattern in each cell, by row from the top
+    cells: Vec<Vec<u32>>,
+}
+
+impl Canvas {
+    fn new(face_width: u32, face_height: u32) -> Canvas {
+        Canvas {
+            width: face_width * 2,
+            height: face_height * 4,
+            cells: vec![vec![0; face_width as usize]; face_height as usize],
+        }
+    }
+
+    /// Set the dot at `x` across and `y` up from where the axes meet, ignoring any off the face
+    fn set(&mut self, x: i32, y: i32) {
+        if x < 2 || y < 1 || x > self.width as i32 + 1 || y > self.height as i32 {
+            return;
+        }
+        let (column, row) = (x as usize - 2, (self.height as i32 - y) as usize);
+        self.cells[row / 4][column / 2] |= DOT_BITS[row % 4][column % 2];
+    }
+
+    fn render(&self) -> String {
+        self.cells
+            .iter()
+            .map(|line| {
+                line.iter()
+                    .map(|&bits| match bits {
+                        // Blank cells are left as spaces so that they can be overlaid
+                        0 => ' ',
+                        _ => std::char::from_u32(0x2800 + bits).unwrap_or(' '),
+                    })
+                    .collect()
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
+/// Given a value, calculate how many dots from the axis it should be plotted
+fn value_to_dot_offset(value: f64, axis: &axis::ContinuousAxis, face_dots: u32) -> f64 {
+    axis.fraction(value) * f64::from(face_dots)
+}
+
+/// Given a scatter plot,
+/// the x ands y-axes
+/// and the face height and width,
+/// create the strings to be drawn as the face
+pub fn render_face_points(
+    s: &[(f64, f64)],
+    x_axis: &axis::ContinuousAxis,
+    y_axis: &axis::ContinuousAxis,
+    face_width: u32,
+    face_height: u32,
+) -> String {
+    let mut canvas = Canvas::new(face_width, face_height);
+    for &(x, y) in s {
+        let x = value_to_dot_offset(x, x_axis, canvas.width).round();
+        let y = value_to_dot_offset(y, y_axis, canvas.height).round();
+        if x.is_finite() && y.is_finite() {
+            canvas.set(x as i32, y as i32);
+        }
+    }
+    canvas.render()
+}
+
+/// Given a line plot,
+/// the x ands y-axes
+/// and the face height and width,
+/// create the strings to be drawn as the face
+pub fn render_face_line(
+    s: &[(f64, f64)],
+    x_axis: &axis::ContinuousAxis,
+    y_axis: &axis::ContinuousAxis,
+    face_width: u32,
+    face_height: u32,
+) -> String {
+    let mut canvas = Canvas::new(face_width, face_height);
+    let points: Vec<_> = s
+        .iter()
+        .filter(|(x, y)| x.is_finite() && y.is_finite())
+        .map(|&(x, y)| {
+            (
+                value_to_dot_offset(x, x_axis, canvas.width),
+                value_to_dot_offset(y, y_axis, canvas.height),
+            )
+        })
+        .collect();
+
+    let top_right = (f64::from(canvas.width), f64::from(canvas.height));
+    for (&start, &end) in points.pairwise() {
+        if let Some((start, end)) = clip_segment(start, end, (0., 0.), top_right) {
+            let start = (start.0.round() as i32, start.1.round() as i32);
+            let end = (end.0.round() as i32, end.1.round() as i32);
+            bresenham(start, end, |x, y| canvas.set(x, y));
+        }
+    }
+    canvas.render()
+}
+
+/// Given the upper and lower edges of an area,
+/// the x ands y-axes
+/// and the face height and width,
+/// create the strings to be drawn as the face
+///
+/// Each column of dots is filled between where the two edges cross it.
+pub fn render_face_area(
+    upper: &[(f64, f64)],
+    lower: &[(f64, f64)],
+    x_axis: &axis::ContinuousAxis,
+    y_axis: &axis::ContinuousAxis,
+    face_width: u32,
+    face_height: u32,
+) -> String {
+    let mut canvas = Canvas::new(face_width, face_height);
+    let upper = area_dots(upper, x_axis, y_axis, &canvas);
+    let lower = area_dots(lower, x_axis, y_axis, &canvas);
+    fill_columns(&mut canvas, &upper, &lower, 0);
+    canvas.render()
+}
+
+/// Like `render_face_area`, for a layer of a stacked area which sits on another layer.
+///
+/// Filling starts from the dot above the lower edge, which is left to the layer below.
+pub fn render_face_stacked_layer(
+    upper: &[(f64, f64)],
+    lower: &[(f64, f64)],
+    x_axis: &axis::ContinuousAxis,
+    y_axis: &axis::ContinuousAxis,
+    face_width: u32,
+    face_height: u32,
+) -> String {
+    let mut canvas = Canvas::new(face_width, face_height);
+    let upper = area_dots(upper, x_axis, y_axis, &canvas);
+    let lower = area_dots(lower, x_axis, y_axis, &canvas);
+    fill_columns(&mut canvas, &upper, &lower, 1);
+    canvas.render()
+}
+
+/// The unrounded dot offsets of the points along an edge of an area
+fn area_dots(
+    s: &[(f64, f64)],
+    x_axis: &axis::ContinuousAxis,
+    y_axis: &axis::ContinuousAxis,
+    canvas: &Canvas,
+) -> Vec<(f64, f64)> {
+    s.iter()
+        .map(|&(x, y)| {
+            (
+                value_to_dot_offset(x, x_axis, canvas.width),
+                value_to_dot_offset(y, y_axis, canvas.height),
+            )
+        })
+        .filter(|(x, y)| x.is_finite() && y.is_finite())
+        .collect()
+}
+
+/// Fill each column of dots between where the two edges cross it,
+/// starting `skip` dots above the lower edge
+fn fill_columns(canvas: &mut Canvas, upper: &[(f64, f64)], lower: &[(f64, f64)], skip: i32) {
+    for x in 2..=canvas.width as i32 + 1 {
+        if let (Some(a), Some(b)) = (
+            interpolate(upper, f64::from(x)),
+            interpolate(lower, f64::from(x)),
+        ) {
+            let (low, high) = (a.min(b).round() as i32, a.max(b).round() as i32);
+            for y in (low + skip).max(1)..=high.min(canvas.height as i32) {
+                canvas.set(x, y);
+            }
+        }
+    }
+}
+
+/// Given the ends of some error bars,
+/// the x ands y-axes
+/// and the face height and width,
+/// create the strings to be drawn as the face
+///
+/// Unless the style's cap width is zero, each end on the face is capped with a dot either side.
+pub fn render_face_error_bars(
+    bars: &[repr::ErrorBar],
+    x_axis: &axis::ContinuousAxis,
+    y_axis: &axis::ContinuousAxis,
+    face_width: u32,
+    face_height: u32,
+    style: &style::ErrorBarStyle,
+) -> String {
+    let mut canvas = Canvas::new(face_width, face_height);
+    let top_right = (f64::from(canvas.width), f64::from(canvas.height));
+    let on_face = |(x, y): (f64, f64)| x >= 0. && y >= 0. && x <= top_right.0 && y <= top_right.1;
+    for &((x1, y1), (x2, y2)) in bars {
+        let start = (
+            value_to_dot_offset(x1, x_axis, canvas.width),
+            value_to_dot_offset(y1, y_axis, canvas.height),
+        );
+        let end = (
+            value_to_dot_offset(x2, x_axis, canvas.width),
+            value_to_dot_offset(y2, y_axis, canvas.height),
+        );
+        if let Some((clipped_start, clipped_end)) = clip_segment(start, end, (0., 0.), top_right) {
+            let clipped_start = (
+                clipped_start.0.round() as i32,
+                clipped_start.1.round() as i32,
+            );
+            let clipped_end = (clipped_end.0.round() as i32, clipped_end.1.round() as i32);
+            bresenham(clipped_start, clipped_end, |x, y| canvas.set(x, y));
+        }
+        if style.get_cap_width() > 0. {
+            let across = if y1 == y2 { (0, 1) } else { (1, 0) };
+            for &end in &[start, end] {
+                if on_face(end) {
+                    let (x, y) = (end.0.round() as i32, end.1.round() as i32);
+                    canvas.set(x - across.0, y - across.1);
+                    canvas.set(x + across.0, y + across.1);
+                }
+            }
+        }
+    }
+    canvas.render()
+}
+
+/// Given a histogram,
+/// the x ands y-axes
+/// and the face height and width,
+/// create the strings to be drawn as the face
+pub fn render_face_bars(
+    h: &repr::Histogram,
+    x_axis: &axis::ContinuousAxis,
+    y_axis: &axis::ContinuousAxis,
+    face_width: u32,
+    face_height: u32,
+) -> String {
+    let mut canvas = Canvas::new(face_width, face_height);
+    // Bars stand on the minimum of the y-axis, which is at the top of the face if it is reversed
+    let base = value_to_dot_offset(y_axis.min(), y_axis, canvas.height).round() as i32;
+
+    // The height in dots of the bar at each column of dots
+    let heights: Vec<i32> = (0..=canvas.width)
+        .map(|column| {
+            let x = x_axis.value_at(f64::from(column) / f64::from(canvas.width));
+            h.bin_bounds
+                .pairwise()
+                .zip(h.get_values())
+                .find(|&((&lower, &upper), _)| lower <= x && x < upper)
+                .map_or(base, |(_, &value)| {
+                    let height = value_to_dot_offset(value, y_axis, canvas.height).round();
+                    height.clamp(0., f64::from(canvas.height)) as i32
+                })
+        })
+        .collect();
+
+    // Outline each bar with its top and the edges between neighbouring bars
+    let mut previous = base;
+    for (column, &height) in heights.iter().enumerate() {
+        let column = column as i32;
+        canvas.set(column, height);
+        for y in previous.min(height)..=previous.max(height) {
+            canvas.set(column, y);
+        }
+        previous = height;
+    }
+    canvas.render()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_canvas() {
+        let mut canvas = Canvas::new(2, 1);
+        assert_eq!(canvas.render(), "  ");
+        canvas.set(2, 4);
+        canvas.set(3, 1);
+        canvas.set(5, 1);
+        // Dots on the axes and beyond the face are skipped
+        canvas.set(1, 2);
+        canvas.set(3, 0);
+        canvas.set(6, 1);
+        assert_eq!(canvas.render(), "⢁⢀");
+    }
+
+    #[test]
+    fn test_render_face_line() {
+        let x_axis = axis::ContinuousAxis::new(0., 4., 6);
+        let y_axis = axis::ContinuousAxis::new(0., 4., 6);
+        let strings = render_face_line(&[(0., 0.), (4., 4.)], &x_axis, &y_axis, 2, 1);
+        assert_eq!(strings, "⠔⠁");
+    }
+
+    #[test]
+    fn test_render_face_area() {
+        let x_axis = axis::ContinuousAxis::new(0., 4., 6);
+        let y_axis = axis::ContinuousAxis::new(0., 4., 6);
+        let strings = render_face_area(
+            &[(0., 4.), (4., 4.)],
+            &[(0., 2.), (4., 2.)],
+            &x_axis,
+            &y_axis,
+            2,
+            1,
+        );
+        // Dots are filled from the lower edge up, as far as the last column the edges reach
+        assert_eq!(strings, "⠿⠇");
+
+        // A layer sitting on another leaves the dots of its lower edge to the one below
+        let strings = render_face_stacked_layer(
+            &[(0., 4.), (4., 4.)],
+            &[(0., 2.), (4., 2.)],
+            &x_axis,
+            &y_axis,
+            2,
+            1,
+        );
+        assert_eq!(strings, "⠛⠃");
+    }
+
+    #[test]
+    fn test_render_face_bars() {
+        let data = [0., 1., 1., 2., 2., 2.];
+        let h = repr::Histogram::from_slice(&data, repr::HistogramBins::Count(3));
+        let x_axis = axis::ContinuousAxis::new(0., 2., 6);
+        let y_axis = axis::ContinuousAxis::new(0., 3., 6);
+        let strings = render_face_bars(&h, &x_axis, &y_axis, 3, 1);
+        assert_eq!(strings, "⡖⠋⡇");
+    }
+}
@@ -1,3 +1 @@
-use failure;
-
 pub type Result<T> = ::std::result::Result<T, failure::Error>;
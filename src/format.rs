@@ -0,0 +1,199 @@
+#![deny(missing_docs)]
+
+//! Configure how the tick labels of a continuous axis are written.
+//!
+//! By default each tick is labelled with its value as Rust would print it.
+//! A `TickFormat` set on a `ContinuousView` is used for the labels of both the SVG and text output.
+//!
+//! # Examples
+//!
+//! ```rust
+//! # use plotlib::repr::Plot;
+//! # use plotlib::view::ContinuousView;
+//! use plotlib::format::TickFormat;
+//!
+//! let p = Plot::new(vec![(0., 0.05), (1e6, 0.3), (2e6, 0.25)]);
+//! let v = ContinuousView::new()
+//!     .add(p)
+//!     .x_tick_format(TickFormat::SI(1))
+//!     .y_tick_format(TickFormat::Percent(0));
+//! ```
+
+use std::fmt;
+use std::rc::Rc;
+
+use crate::time;
+
+/// The prefixes used by `TickFormat::SI`, starting at 10<sup>-12</sup>
+const SI_PREFIXES: [&str; 9] = ["p", "n", "µ", "m", "", "k", "M", "G", "T"];
+
+/// How to write the value of a tick as its label
+#[derive(Clone, Default)]
+pub enum TickFormat {
+    /// The value as Rust prints it, e.g. `1000000` or `0.25`
+    #[default]
+    Plain,
+    /// The value with the given number of decimal places, e.g. `Fixed(2)` gives `0.25` as `0.25` and `3` as `3.00`
+    Fixed(usize),
+    /// The value in scientific notation with the given number of decimal places,
+    /// e.g. `Scientific(1)` gives `1500000` as `1.5e6`
+    Scientific(usize),
+    /// The value scaled by a metric prefix such as `k`, `M` or `G`,
+    /// with at most the given number of decimal places, e.g. `SI(1)` gives `1500000` as `1.5M`
+    SI(usize),
+    /// The value multiplied by 100 with a `%` sign and the given number of decimal places,
+    /// e.g. `Percent(0)` gives `0.25` as `25%`
+    Percent(usize),
+    /// The value as an amount of money with the given symbol, decimal places and thousands separators,
+    /// e.g. `Currency("$".into(), 2)` gives `-1234.5` as `-$1,234.50`
+    Currency(String, usize),
+    /// The value as a timestamp in seconds since the Unix epoch, written with a pattern.
+    /// See the `time` module for the fields which may be used.
+    Time(String),
+    /// The value passed to a function
+    Custom(Rc<dyn Fn(f64) -> String>),
+}
+
+impl TickFormat {
+    /// Label ticks with the result of a function
+    pub fn custom<F>(f: F) -> Self
+    where
+        F: Fn(f64) -> String + 'static,
+    {
+        TickFormat::Custom(Rc::new(f))
+    }
+
+    /// Write a value as a tick label
+    pub fn format(&self, value: f64) -> String {
+        match self {
+            TickFormat::Plain => value.to_string(),
+            TickFormat::Fixed(places) => format!("{:.*}", places, value),
+            TickFormat::Scientific(places) => format!("{:.*e}", places, value),
+            TickFormat::SI(places) => format_si(value, *places),
+            TickFormat::Percent(places) => format!("{:.*}%", places, value * 100.),
+            TickFormat::Currency(symbol, places) => format_currency(value, symbol, *places),
+            TickFormat::Time(pattern) => time::format_time(value, pattern),
+            TickFormat::Custom(f) => f(value),
+        }
+    }
+}
+
+impl fmt::Debug for TickFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TickFormat::Plain => write!(f, "Plain"),
+            TickFormat::Fixed(places) => write!(f, "Fixed({})", places),
+            TickFormat::Scientific(places) => write!(f, "Scientific({})", places),
+            TickFormat::SI(places) => write!(f, "SI({})", places),
+            TickFormat::Percent(places) => write!(f, "Percent({})", places),
+            TickFormat::Currency(symbol, places) => write!(f, "Currency({:?}, {})", symbol, places),
+            TickFormat::Time(pattern) => write!(f, "Time({:?})", pattern),
+            TickFormat::Custom(_) => write!(f, "Custom(..)"),
+        }
+    }
+}
+
+/// Remove trailing zeros after the decimal point, and the point itself if nothing follows it
+fn trim_zeros(s: String) -> String {
+    if s.contains('.') {
+        s.trim_end_matches('0').trim_end_matches('.').to_string()
+    } else {
+        s
+    }
+}
+
+fn format_si(value: f64, places: usize) -> String {
+    if value == 0. || !value.is_finite() {
+        return value.to_string();
+    }
+    let exponent = (value.abs().log10() / 3.).floor() as i32;
+    let mut exponent = exponent.clamp(-4, 4);
+    let rounded = |exponent: i32| format!("{:.*}", places, value / 1000f64.powi(exponent));
+    let mut scaled = rounded(exponent);
+    // Rounding can carry up to 1000, e.g. 999.96 to one place, which is written with the next prefix
+    if exponent < 4 && scaled.parse::<f64>().is_ok_and(|s| s.abs() >= 1000.) {
+        exponent += 1;
+        scaled = rounded(exponent);
+    }
+    let prefix = SI_PREFIXES[(exponent + 4) as usize];
+    format!("{}{}", trim_zeros(scaled), prefix)
+}
+
+fn format_currency(value: f64, symbol: &str, places: usize) -> String {
+    let digits = format!("{:.*}", places, value.abs());
+    let (whole, fraction) = match digits.find('.') {
+        Some(point) => digits.split_at(point),
+        None => (digits.as_str(), ""),
+    };
+    let mut grouped = String::new();
+    for (i, c) in whole.chars().enumerate() {
+        if i > 0 && (whole.len() - i).is_multiple_of(3) {
+            grouped.push(',');
+        }
+        grouped.push(c);
+    }
+    // Don't show "-$0.00" for values which round to zero
+    let sign = if value < 0. && digits.chars().any(|c| c.is_ascii_digit() && c != '0') {
+        "-"
+    } else {
+        ""
+    };
+    format!("{}{}{}{}", sign, symbol, grouped, fraction)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_formats() {
+        assert_eq!(TickFormat::Plain.format(1e6), "1000000");
+        assert_eq!(TickFormat::Fixed(2).format(0.1 + 0.2), "0.30");
+        assert_eq!(TickFormat::Fixed(0).format(2.5e3), "2500");
+        assert_eq!(TickFormat::Scientific(1).format(1.5e6), "1.5e6");
+        assert_eq!(TickFormat::Scientific(2).format(-0.00042), "-4.20e-4");
+        assert_eq!(TickFormat::Percent(0).format(0.25), "25%");
+        assert_eq!(TickFormat::Percent(1).format(0.125), "12.5%");
+        assert_eq!(TickFormat::custom(|v| format!("<{}>", v)).format(3.), "<3>");
+        assert_eq!(TickFormat::Time("%Y".into()).format(0.), "1970");
+    }
+
+    #[test]
+    fn test_si() {
+        let si = TickFormat::SI(1);
+        assert_eq!(si.format(0.), "0");
+        assert_eq!(si.format(1500.), "1.5k");
+        assert_eq!(si.format(2e6), "2M");
+        assert_eq!(si.format(-3.25e9), "-3.2G");
+        assert_eq!(si.format(999.), "999");
+        assert_eq!(si.format(0.005), "5m");
+        assert_eq!(si.format(4.2e-6), "4.2µ");
+    }
+
+    #[test]
+    fn test_si_carry() {
+        let si = TickFormat::SI(1);
+        assert_eq!(si.format(999.94), "999.9");
+        assert_eq!(si.format(999.96), "1k");
+        assert_eq!(si.format(1000.), "1k");
+        assert_eq!(si.format(999_960.), "1M");
+        assert_eq!(si.format(-999_960.), "-1M");
+        assert_eq!(si.format(0.99996), "1");
+        assert_eq!(si.format(999.96e-6), "1m");
+        assert_eq!(TickFormat::SI(0).format(999.5), "1k");
+        // There is no prefix beyond T to carry to
+        assert_eq!(si.format(999.96e12), "1000T");
+    }
+
+    #[test]
+    fn test_currency() {
+        let currency = TickFormat::Currency("$".into(), 2);
+        assert_eq!(currency.format(-1234.5), "-$1,234.50");
+        assert_eq!(currency.format(999.), "$999.00");
+        assert_eq!(currency.format(-0.001), "$0.00");
+        assert_eq!(
+            TickFormat::Currency("£".into(), 0).format(1234567.),
+            "£1,234,567"
+        );
+    }
+}
@@ -9,6 +9,8 @@
 //!
 //! The grid lines for `plotlib` are rendered
 //! _underneath_ the data so as to not detract from the data.
+//! In text output the lines are drawn with light dashed glyphs,
+//! with dotted lines at any minor ticks.
 //!
 //! # Examples
 //!
@@ -28,18 +30,38 @@
 //!
 //! // Render plot
 //! ```
+//!
+//! Rather than spacing the lines evenly, they can be drawn at the ticks of the axes,
+//! with fainter lines at the minor ticks:
+//!
+//! ```rust
+//! # use plotlib::view::ContinuousView;
+//! use plotlib::grid::Grid;
+//! # use plotlib::view::View;
+//!
+//! let mut v = ContinuousView::new().x_minor_ticks(5);
+//! v.add_grid(Grid::at_ticks().minor("lightgrey", 0.5));
+//! ```
+
+use crate::axis;
 
-// Internal type representing the logic of when do we render only horizontal lines, and when do we
-// render a full grid
-pub(crate) enum GridType<'a> {
-    HorizontalOnly(&'a Grid),
-    Both(&'a Grid),
+/// Where the lines of a grid are drawn
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum GridPlacement {
+    /// `nx` vertical and `ny` horizontal lines spaced evenly across the face (the default).
+    /// For categorical plots, only horizontal lines will be shown.
+    #[default]
+    Even,
+    /// At the major ticks of each continuous axis and the middle of each category
+    Ticks,
+    /// At the major ticks of each continuous axis and between each category
+    Boundaries,
 }
 
 /// Configuration for the grid on a plot
 ///
-/// Supports changing the number of grid lines for the x and y dimensions.
-/// **Note:** for categorical plots, only horizontal lines will be shown.
+/// Supports changing the number of grid lines for the x and y dimensions,
+/// or placing them at the ticks of the axes.
 pub struct Grid {
     /// Number of vertical grid lines (defaults to 3)
     pub nx: u32,
@@ -47,6 +69,13 @@ pub struct Grid {
     pub ny: u32,
     /// Color of the grid lines (defaults to "darkgrey")
     pub color: String,
+    /// Width of the grid lines (defaults to 1)
+    pub width: f32,
+    /// Where the grid lines are drawn (defaults to `GridPlacement::Even`)
+    pub placement: GridPlacement,
+    /// Colour and width of lines at the minor ticks of continuous axes.
+    /// These are only drawn if the grid is placed at the ticks (defaults to `None`)
+    pub minor: Option<(String, f32)>,
 }
 
 impl Default for Grid {
@@ -64,6 +93,157 @@ impl Grid {
             nx,
             ny,
             color: "darkgrey".to_owned(),
+            width: 1.,
+            placement: GridPlacement::Even,
+            minor: None,
         }
     }
+
+    /// Create a new grid with lines at the major ticks of the axes
+    pub fn at_ticks() -> Grid {
+        Grid {
+            placement: GridPlacement::Ticks,
+            ..Grid::default()
+        }
+    }
+
+    /// Set the width of the grid lines
+    pub fn width(mut self, width: f32) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Set where the grid lines are drawn
+    pub fn placement(mut self, placement: GridPlacement) -> Self {
+        self.placement = placement;
+        self
+    }
+
+    /// Also draw lines at the minor ticks, with their own colour and width
+    pub fn minor<T>(mut self, color: T, width: f32) -> Self
+    where
+        T: Into<String>,
+    {
+        self.minor = Some((color.into(), width));
+        self
+    }
+
+    /// Where to draw lines across a continuous axis, as fractions of the way along it
+    fn continuous_lines(&self, axis: &axis::ContinuousAxis, count: u32) -> (Vec<f64>, Vec<f64>) {
+        match self.placement {
+            GridPlacement::Even => (even_lines(count), vec![]),
+            GridPlacement::Ticks | GridPlacement::Boundaries => {
+                let fractions = |ticks: &[f64]| ticks.iter().map(|&t| axis.fraction(t)).collect();
+                let minor = match self.minor {
+                    Some(_) => fractions(axis.minor_ticks()),
+                    None => vec![],
+                };
+                (fractions(axis.ticks()), minor)
+            }
+        }
+    }
+
+    /// The lines to draw on the face of a view with continuous axes
+    pub(crate) fn continuous(
+        &self,
+        x_axis: &axis::ContinuousAxis,
+        y_axis: &axis::ContinuousAxis,
+    ) -> GridLines {
+        let (x, minor_x) = self.continuous_lines(x_axis, self.nx);
+        let (y, minor_y) = self.continuous_lines(y_axis, self.ny);
+        GridLines {
+            x,
+            y,
+            minor_x,
+            minor_y,
+        }
+    }
+
+    /// The lines to draw on the face of a view with a categorical x-axis
+    pub(crate) fn categorical(
+        &self,
+        x_axis: &axis::CategoricalAxis,
+        y_axis: &axis::ContinuousAxis,
+    ) -> GridLines {
+        let categories = x_axis.ticks().len();
+        let x = match self.placement {
+            GridPlacement::Even => vec![],
+            GridPlacement::Ticks => (0..categories)
+                .map(|i| (i as f64 + 0.5) / categories as f64)
+                .collect(),
+            GridPlacement::Boundaries => (0..=categories)
+                .map(|i| i as f64 / categories as f64)
+                .collect(),
+        };
+        let (y, minor_y) = self.continuous_lines(y_axis, self.ny);
+        GridLines {
+            x,
+            y,
+            minor_x: vec![],
+            minor_y,
+        }
+    }
+}
+
+/// The fractions of the way across the face of `count` evenly spaced lines, and the edges
+fn even_lines(count: u32) -> Vec<f64> {
+    (0..=count)
+        .map(|i| f64::from(i) / f64::from(count))
+        .collect()
+}
+
+/// The positions of grid lines on a face, as fractions of the way along each axis
+#[derive(Debug, PartialEq)]
+pub(crate) struct GridLines {
+    pub x: Vec<f64>,
+    pub y: Vec<f64>,
+    pub minor_x: Vec<f64>,
+    pub minor_y: Vec<f64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scale::Scale;
+
+    #[test]
+    fn test_even_lines() {
+        let x_axis = axis::ContinuousAxis::new(0., 7., 6);
+        let y_axis = axis::ContinuousAxis::new(0., 1., 6);
+        let lines = Grid::new(2, 4).continuous(&x_axis, &y_axis);
+        assert_eq!(lines.x, [0., 0.5, 1.]);
+        assert_eq!(lines.y, [0., 0.25, 0.5, 0.75, 1.]);
+        assert!(lines.minor_x.is_empty());
+    }
+
+    #[test]
+    fn test_tick_lines() {
+        let x_axis = axis::ContinuousAxis::new(-1., 7., 6).subdivide(2);
+        let y_axis = axis::ContinuousAxis::scaled(1., 100., 6, Scale::Log10);
+        let lines = Grid::at_ticks().continuous(&x_axis, &y_axis);
+        assert_eq!(lines.x, [0.125, 0.375, 0.625, 0.875]);
+        assert_eq!(lines.y, [0., 0.5, 1.]);
+        assert!(lines.minor_x.is_empty());
+
+        let lines = Grid::at_ticks()
+            .minor("lightgrey", 0.5)
+            .continuous(&x_axis, &y_axis);
+        assert_eq!(lines.minor_x, [0., 0.25, 0.5, 0.75, 1.]);
+        assert_eq!(lines.minor_y.len(), 16);
+    }
+
+    #[test]
+    fn test_categorical_lines() {
+        let ticks = ["a".to_string(), "b".to_string()];
+        let x_axis = axis::CategoricalAxis::new(&ticks);
+        let y_axis = axis::ContinuousAxis::new(0., 4., 6);
+        assert!(Grid::new(3, 3).categorical(&x_axis, &y_axis).x.is_empty());
+        let centres = Grid::at_ticks().categorical(&x_axis, &y_axis);
+        assert_eq!(centres.x, [0.25, 0.75]);
+        assert_eq!(centres.y, [0., 0.25, 0.5, 0.75, 1.]);
+        let boundaries = Grid::at_ticks()
+            .placement(GridPlacement::Boundaries)
+            .categorical(&x_axis, &y_axis);
+        assert_eq!(boundaries.x, [0., 0.5, 1.]);
+    }
 }
@@ -58,8 +58,8 @@ Example
 
 - *Data*: A linear sequence of numbers as a Vec<f64>
 - *Representation*: A binned histogram, stored as a list of `Bin`s,
-                each of which is the bounds and the counts.
-                Blue bars with no casing.
+  each of which is the bounds and the counts.
+  Blue bars with no casing.
 - *View*: Dimension 0 mapped to x-axis with range 5-19 and counts mapped to y-axis with range 0-60
 - *Page*: A single view on the page
 - *Rendering*: An SVG
@@ -73,14 +73,26 @@ in this case, interpreting the bins and colours to create SVG elements.
 
 */
 
+pub mod format;
 pub mod grid;
 pub mod page;
+pub mod range;
+pub mod report;
 pub mod repr;
+pub mod scale;
 pub mod style;
+pub mod terminal;
+pub mod ticks;
+pub mod time;
 pub mod view;
 
 mod axis;
+mod braille_render;
+mod colour;
 mod errors;
+mod pdf_render;
+mod png_render;
+mod scene;
 mod svg_render;
 mod text_render;
 mod utils;
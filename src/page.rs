@@ -10,17 +10,59 @@ use svg::Document;
 use svg::Node;
 
 use crate::errors::Result;
+use crate::pdf_render;
+use crate::png_render;
+use crate::scene::Scene;
+use crate::svg_render;
+use crate::terminal::Colours;
+use crate::text_render;
 use crate::view::View;
 
+use failure::format_err;
 use failure::ResultExt;
 
+/**
+A position in a page's layout grid, counted from zero at the top left,
+which may span several rows and columns
+*/
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cell {
+    row: u32,
+    column: u32,
+    row_span: u32,
+    column_span: u32,
+}
+
+impl Cell {
+    /// A cell covering a single row and column
+    pub fn new(row: u32, column: u32) -> Self {
+        Cell {
+            row,
+            column,
+            row_span: 1,
+            column_span: 1,
+        }
+    }
+
+    /// Set the number of rows and columns which the cell covers
+    pub fn span(mut self, rows: u32, columns: u32) -> Self {
+        self.row_span = rows;
+        self.column_span = columns;
+        self
+    }
+}
+
 /**
 A single page page laying out the views in a grid
 */
 pub struct Page<'a> {
     views: Vec<&'a dyn View>,
+    cells: Vec<Option<Cell>>,
     num_views: u32,
     dimensions: (u32, u32),
+    layout: Option<(u32, u32)>,
+    gap: u32,
+    text_colours: Colours,
 }
 
 impl<'a> Page<'a> {
@@ -30,8 +72,12 @@ impl<'a> Page<'a> {
     pub fn empty() -> Self {
         Page {
             views: Vec::new(),
+            cells: Vec::new(),
             num_views: 0,
             dimensions: (600, 400),
+            layout: None,
+            gap: 0,
+            text_colours: Colours::None,
         }
     }
 
@@ -48,33 +94,169 @@ impl<'a> Page<'a> {
         self
     }
 
-    /// Add a view to the plot
+    /**
+    Set the number of rows and columns in the layout grid.
+
+    Without this, the grid is chosen to be as square as possible
+    while still having a cell for every view.
+    */
+    pub fn layout(mut self, rows: u32, columns: u32) -> Self {
+        self.layout = Some((rows, columns));
+        self
+    }
+
+    /// Set the space left between neighbouring cells of the layout grid
+    pub fn gap(mut self, gap: u32) -> Self {
+        self.gap = gap;
+        self
+    }
+
+    /**
+    Set the colours used by `to_text` and `to_braille`.
+
+    Each representation is drawn in its own colour using ANSI escape sequences.
+    The default of `Colours::None` gives plain text,
+    and `Colours::detect()` chooses what standard output supports.
+    */
+    pub fn text_colours(mut self, colours: Colours) -> Self {
+        self.text_colours = colours;
+        self
+    }
+
+    /// Add a view to the plot, in the first free cell of the layout grid
     pub fn add_plot(mut self, view: &'a dyn View) -> Self {
         self.views.push(view);
+        self.cells.push(None);
         self.num_views += 1;
         self
     }
 
+    /// Add a view to the plot in a particular cell of the layout grid
+    pub fn add_plot_at(mut self, view: &'a dyn View, cell: Cell) -> Self {
+        self.views.push(view);
+        self.cells.push(Some(cell));
+        self.num_views += 1;
+        self
+    }
+
+    /**
+    Work out where every view goes.
+
+    Returns the number of rows and columns in the grid along with the cell of each view.
+    */
+    fn place_views(&self) -> Result<((u32, u32), Vec<Cell>)> {
+        for cell in self.cells.iter().flatten() {
+            if cell.row_span == 0 || cell.column_span == 0 {
+                return Err(format_err!("Cell spans must be at least 1: {:?}", cell));
+            }
+        }
+        let (mut rows, columns) = match self.layout {
+            Some((0, _)) | Some((_, 0)) => {
+                return Err(format_err!("Invalid layout: {:?}", self.layout));
+            }
+            Some(layout) => layout,
+            None => {
+                let columns = (f64::from(self.num_views).sqrt().ceil() as u32).max(1);
+                let rows = self.num_views.div_ceil(columns).max(1);
+                let (bottom, right) =
+                    self.cells
+                        .iter()
+                        .flatten()
+                        .fold((0, 0), |(bottom, right), cell| {
+                            (
+                                u32::max(bottom, cell.row + cell.row_span),
+                                u32::max(right, cell.column + cell.column_span),
+                            )
+                        });
+                (rows.max(bottom), columns.max(right))
+            }
+        };
+
+        let mut occupied = vec![vec![false; columns as usize]; rows as usize];
+        for cell in self.cells.iter().flatten() {
+            claim(&mut occupied, cell)?;
+        }
+
+        let mut placed = vec![];
+        for cell in &self.cells {
+            let cell = match cell {
+                Some(cell) => *cell,
+                None => {
+                    let free = (0..rows * columns)
+                        .map(|i| Cell::new(i / columns, i % columns))
+                        .find(|c| !occupied[c.row as usize][c.column as usize]);
+                    let cell = match (free, self.layout) {
+                        (Some(cell), _) => cell,
+                        (None, None) => {
+                            // An automatic layout grows downwards to fit everything in
+                            rows += 1;
+                            occupied.push(vec![false; columns as usize]);
+                            Cell::new(rows - 1, 0)
+                        }
+                        (None, Some(_)) => {
+                            return Err(format_err!(
+                                "The {}x{} layout has no room for {} views",
+                                rows,
+                                columns,
+                                self.num_views
+                            ));
+                        }
+                    };
+                    claim(&mut occupied, &cell)?;
+                    cell
+                }
+            };
+            placed.push(cell);
+        }
+        Ok(((rows, columns), placed))
+    }
+
     /**
     Render the plot to an svg document
     */
     pub fn to_svg(&self) -> Result<svg::Document> {
         let (width, height) = self.dimensions;
         let mut document = Document::new().set("viewBox", (0, 0, width, height));
+        svg_render::reset_clip_ids();
+
+        let x_margin = 120.; // should actually depend on y-axis label font size
+        let y_margin = 60.;
+        let x_offset = 0.6 * x_margin;
+        let y_offset = 0.6 * y_margin;
 
-        let x_margin = 120; // should actually depend on y-axis label font size
-        let y_margin = 60;
-        let x_offset = 0.6 * f64::from(x_margin);
-        let y_offset = 0.6 * f64::from(y_margin);
-
-        // TODO put multiple views in correct places
-        for &view in &self.views {
-            let view_group = view
-                .to_svg(f64::from(width - x_margin), f64::from(height - y_margin))?
-                .set(
-                    "transform",
-                    format!("translate({}, {})", x_offset, f64::from(height) - y_offset),
-                );
+        let ((rows, columns), cells) = self.place_views()?;
+        let gap = f64::from(self.gap);
+        for (&view, cell) in self.views.iter().zip(cells) {
+            let (x, cell_width) = cell_extent(
+                f64::from(width),
+                gap,
+                columns,
+                cell.column,
+                cell.column_span,
+            );
+            let (y, cell_height) =
+                cell_extent(f64::from(height), gap, rows, cell.row, cell.row_span);
+            // Leave as much room for a right-hand axis as for the left
+            let x_margin = if view.has_secondary_axis() {
+                2. * x_offset
+            } else {
+                x_margin
+            };
+            let (face_width, face_height) = (cell_width - x_margin, cell_height - y_margin);
+            if face_width <= 0. || face_height <= 0. {
+                return Err(format_err!(
+                    "The page is too small to fit a view in {:?}",
+                    cell
+                ));
+            }
+            let view_group = view.to_svg(face_width, face_height)?.set(
+                "transform",
+                format!(
+                    "translate({}, {})",
+                    x + x_offset,
+                    y + cell_height - y_offset
+                ),
+            );
             document.append(view_group);
         }
         Ok(document)
@@ -82,20 +264,118 @@ impl<'a> Page<'a> {
 
     /**
     Render the plot to an `String`
+
+    The dimensions and gap are measured in characters.
+    Each view's face is given the size of its cell in the layout grid
+    and the rows and columns are then widened to fit the axes drawn around them.
     */
     pub fn to_text(&self) -> Result<String> {
+        self.compose_text(|view, face_width, face_height| match self.text_colours {
+            Colours::None => view.to_text(face_width, face_height),
+            colours => view.to_coloured_text(face_width, face_height, false, colours),
+        })
+    }
+
+    /**
+    Render the plot to an `String` using Unicode braille patterns
+
+    This is laid out like `to_text` but each character of a plot's face holds 2×4 dots,
+    giving lines, points and histograms eight times the resolution.
+    */
+    pub fn to_braille(&self) -> Result<String> {
+        self.compose_text(|view, face_width, face_height| match self.text_colours {
+            Colours::None => view.to_braille(face_width, face_height),
+            colours => view.to_coloured_text(face_width, face_height, true, colours),
+        })
+    }
+
+    /// Lay out the text rendered for each view in the same grid as `to_svg`
+    fn compose_text<F>(&self, render_view: F) -> Result<String>
+    where
+        F: Fn(&dyn View, u32, u32) -> Result<String>,
+    {
+        if self.views.is_empty() {
+            return Ok(String::new());
+        }
         let (width, height) = self.dimensions;
-        // TODO compose multiple views into a page
-        let view = self.views[0];
-        view.to_text(width, height)
+        let ((rows, columns), cells) = self.place_views()?;
+        let gap = f64::from(self.gap);
+
+        let mut rendered = vec![];
+        for (&view, cell) in self.views.iter().zip(&cells) {
+            let (_, face_width) = cell_extent(
+                f64::from(width),
+                gap,
+                columns,
+                cell.column,
+                cell.column_span,
+            );
+            let (_, face_height) =
+                cell_extent(f64::from(height), gap, rows, cell.row, cell.row_span);
+            let text = render_view(view, face_width as u32, face_height as u32)?;
+            let text_width = text.split('\n').map(text_render::display_width).max();
+            let text_height = text.split('\n').count();
+            rendered.push((text, text_width.unwrap_or(0), text_height));
+        }
+
+        // Each row and column starts after everything which ends before it
+        let column_starts = table_starts(
+            columns,
+            self.gap as usize,
+            cells
+                .iter()
+                .zip(&rendered)
+                .map(|(c, &(_, w, _))| (c.column, c.column_span, w)),
+        );
+        let row_starts = table_starts(
+            rows,
+            self.gap as usize,
+            cells
+                .iter()
+                .zip(&rendered)
+                .map(|(c, &(_, _, h))| (c.row, c.row_span, h)),
+        );
+
+        let page_width = column_starts[columns as usize].saturating_sub(self.gap as usize);
+        let page_height = row_starts[rows as usize].saturating_sub(self.gap as usize);
+        let mut page_string = text_render::empty_face(page_width as u32, page_height as u32);
+        for (cell, (text, _, _)) in cells.iter().zip(&rendered) {
+            page_string = text_render::overlay(
+                &page_string,
+                text,
+                column_starts[cell.column as usize] as i32,
+                row_starts[cell.row as usize] as i32,
+            );
+        }
+        Ok(page_string)
+    }
+
+    /**
+    Render the plot to the bytes of a PNG image
+
+    The image has the same size in pixels as the page's dimensions.
+    */
+    pub fn to_png(&self) -> Result<Vec<u8>> {
+        let scene = Scene::from_document(&self.to_svg()?)?;
+        png_render::render(&scene)
+    }
+
+    /**
+    Render the plot to the bytes of a single-page PDF document
+
+    The page is the same size in points as the page's dimensions.
+    */
+    pub fn to_pdf(&self) -> Result<Vec<u8>> {
+        let scene = Scene::from_document(&self.to_svg()?)?;
+        Ok(pdf_render::render(&scene))
     }
 
     /**
     Save the plot to a file.
 
     The type of file will be based on the file extension.
+    `.svg`, `.png` and `.pdf` are supported.
     */
-
     pub fn save<P>(&self, path: P) -> Result<()>
     where
         P: AsRef<Path>,
@@ -104,7 +384,197 @@ impl<'a> Page<'a> {
             Some("svg") => svg::save(path, &self.to_svg()?)
                 .context("saving svg")
                 .map_err(From::from),
+            Some("png") => std::fs::write(path, self.to_png()?)
+                .context("saving png")
+                .map_err(From::from),
+            Some("pdf") => std::fs::write(path, self.to_pdf()?)
+                .context("saving pdf")
+                .map_err(From::from),
             _ => Ok(()),
         }
     }
 }
+
+/**
+The position at which each row or column of a text layout starts, plus the end of the last one.
+
+Each entry of `extents` is the first row or column of a view, how many it spans and its size.
+*/
+fn table_starts<I>(count: u32, gap: usize, extents: I) -> Vec<usize>
+where
+    I: Iterator<Item = (u32, u32, usize)>,
+{
+    let extents: Vec<_> = extents.collect();
+    let mut starts = vec![0];
+    for end in 1..=count {
+        let previous = starts[end as usize - 1];
+        let start = extents
+            .iter()
+            .filter(|&&(first, span, _)| first + span == end)
+            .map(|&(first, _, size)| starts[first as usize] + size + gap)
+            .fold(previous, usize::max);
+        starts.push(start);
+    }
+    starts
+}
+
+/// Mark the grid squares covered by a cell as taken
+fn claim(occupied: &mut [Vec<bool>], cell: &Cell) -> Result<()> {
+    let (rows, columns) = (occupied.len() as u32, occupied[0].len() as u32);
+    if cell.row + cell.row_span > rows || cell.column + cell.column_span > columns {
+        return Err(format_err!(
+            "{:?} is outside the {}x{} layout",
+            cell,
+            rows,
+            columns
+        ));
+    }
+    for row in &mut occupied[cell.row as usize..(cell.row + cell.row_span) as usize] {
+        for taken in &mut row[cell.column as usize..(cell.column + cell.column_span) as usize] {
+            if *taken {
+                return Err(format_err!("{:?} overlaps another view", cell));
+            }
+            *taken = true;
+        }
+    }
+    Ok(())
+}
+
+/**
+The start and length of a cell along one direction of the page,
+given the page's length and the gap between cells
+*/
+fn cell_extent(length: f64, gap: f64, count: u32, start: u32, span: u32) -> (f64, f64) {
+    let count = f64::from(count);
+    let cell = (length - gap * (count - 1.)) / count;
+    let start = f64::from(start) * (cell + gap);
+    (start, f64::from(span) * cell + f64::from(span - 1) * gap)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repr::{BarChart, BoxPlot, Plot};
+    use crate::style::{BoxStyle, LineStyle, PointStyle};
+    use crate::view::{CategoricalView, ContinuousView};
+
+    #[test]
+    fn test_automatic_layout() {
+        let v = ContinuousView::new();
+        let page = Page::empty().add_plot(&v).add_plot(&v).add_plot(&v);
+        let (grid, cells) = page.place_views().unwrap();
+        assert_eq!(grid, (2, 2));
+        assert_eq!(
+            cells,
+            vec![Cell::new(0, 0), Cell::new(0, 1), Cell::new(1, 0)]
+        );
+
+        let (grid, cells) = Page::single(&v).place_views().unwrap();
+        assert_eq!(grid, (1, 1));
+        assert_eq!(cells, vec![Cell::new(0, 0)]);
+    }
+
+    #[test]
+    fn test_spanning_layout() {
+        let v = ContinuousView::new();
+        let page = Page::empty()
+            .layout(2, 3)
+            .add_plot(&v)
+            .add_plot_at(&v, Cell::new(0, 1).span(2, 2));
+        let (grid, cells) = page.place_views().unwrap();
+        assert_eq!(grid, (2, 3));
+        assert_eq!(cells[0], Cell::new(0, 0));
+
+        let overlapping = page.add_plot_at(&v, Cell::new(1, 2));
+        assert!(overlapping.place_views().is_err());
+        let outside = Page::empty().layout(1, 1).add_plot_at(&v, Cell::new(0, 1));
+        assert!(outside.place_views().is_err());
+        let full = Page::empty().layout(1, 1).add_plot(&v).add_plot(&v);
+        assert!(full.place_views().is_err());
+    }
+
+    #[test]
+    fn test_table_starts() {
+        let extents = vec![(0, 1, 5), (1, 1, 3), (0, 2, 10)];
+        assert_eq!(table_starts(2, 1, extents.into_iter()), vec![0, 6, 11]);
+        assert_eq!(table_starts(2, 0, vec![].into_iter()), vec![0, 0, 0]);
+    }
+
+    #[test]
+    fn test_text_layout() {
+        let v = ContinuousView::new()
+            .add(Plot::new(vec![(0., 1.), (1., 2.)]).point_style(PointStyle::new()));
+        assert_eq!(Page::empty().to_text().unwrap(), "");
+
+        let single = Page::single(&v).dimensions(20, 10).to_text().unwrap();
+        let (single_width, single_height) = (
+            single.lines().map(|l| l.chars().count()).max().unwrap(),
+            single.lines().count(),
+        );
+
+        let page = Page::empty()
+            .dimensions(42, 10)
+            .gap(2)
+            .add_plot(&v)
+            .add_plot(&v)
+            .to_text()
+            .unwrap();
+        assert_eq!(page.lines().count(), single_height);
+        assert!(page
+            .lines()
+            .all(|l| l.chars().count() == 2 * single_width + 2));
+        for line in page.lines() {
+            let left: String = line.chars().take(single_width + 2).collect();
+            let right: String = line.chars().skip(single_width + 2).collect();
+            assert_eq!(left.trim_end(), right.trim_end());
+        }
+    }
+
+    #[test]
+    fn test_coloured_text() {
+        let p = Plot::new(vec![(0., 1.), (1., 2.)])
+            .point_style(PointStyle::new().colour("#DD3355"))
+            .line_style(LineStyle::new());
+        let v = ContinuousView::new().add(p);
+        let page = Page::single(&v).dimensions(20, 10);
+        let plain = page.to_text().unwrap();
+        assert!(!plain.contains('\x1b'));
+
+        let coloured = page.text_colours(Colours::TrueColour).to_text().unwrap();
+        assert!(coloured.contains("\x1b[38;2;221;51;85m●\x1b[0m"));
+        // The black line is left in the default colour
+        let stripped = coloured
+            .replace("\x1b[38;2;221;51;85m", "")
+            .replace("\x1b[0m", "");
+        assert_eq!(stripped, plain);
+    }
+
+    #[test]
+    fn test_categorical_text() {
+        let v = CategoricalView::new()
+            .add(BarChart::new(5.3).label("first"))
+            .add(
+                BoxPlot::from_vec(vec![1., 2., 3., 6.])
+                    .label("second")
+                    .style(&BoxStyle::new().fill("#DD3355")),
+            )
+            .x_label("Experiment");
+        let page = Page::single(&v).dimensions(30, 10);
+        let plain = page.to_text().unwrap();
+        assert_eq!(plain.lines().count(), 14);
+        assert!(plain.contains("first"));
+        assert!(plain.contains("second"));
+        assert!(plain.contains("Experiment"));
+        assert!(plain.contains('='));
+
+        let coloured = page.text_colours(Colours::Ansi256).to_text().unwrap();
+        assert!(coloured.contains("\x1b[38;5;"));
+    }
+
+    #[test]
+    fn test_cell_extent() {
+        assert_eq!(cell_extent(600., 0., 1, 0, 1), (0., 600.));
+        assert_eq!(cell_extent(620., 10., 3, 1, 1), (210., 200.));
+        assert_eq!(cell_extent(620., 10., 3, 1, 2), (210., 410.));
+    }
+}
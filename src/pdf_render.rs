@@ -0,0 +1,522 @@
+/*!
+A module for writing pages as vector PDF documents.
+
+Like the PNG backend, this draws the `scene::Scene` flattened out of a page's SVG document,
+so every representation that `svg_render` supports is available.
+Shapes are written as PDF path operators and text uses the standard Helvetica font,
+which every PDF reader provides, so nothing needs to be embedded.
+*/
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use crate::colour::Rgb;
+use crate::scene::{Anchor, Item, Join, Scene, Shape, Transform};
+
+/// The standard approximation of a quarter circle with a cubic Bézier curve
+const KAPPA: f64 = 0.552_284_749_8;
+
+/// A PDF document under construction
+pub struct Document {
+    /// The body of each object, where object `n` is at index `n - 1`
+    objects: Vec<Vec<u8>>,
+    pages: Vec<PageEntry>,
+    /// Bookmarks as pairs of title and page object number
+    outline: Vec<(String, usize)>,
+    title: Option<String>,
+}
+
+/// A page whose dictionary is written once all of its links are known
+struct PageEntry {
+    id: usize,
+    dictionary: String,
+    annotations: Vec<usize>,
+}
+
+const CATALOG: usize = 1;
+const PAGES: usize = 2;
+const FONT: usize = 3;
+
+impl Document {
+    pub fn new() -> Document {
+        let mut document = Document {
+            objects: vec![],
+            pages: vec![],
+            outline: vec![],
+            title: None,
+        };
+        // The catalog and page tree are written once all of the pages are known
+        document.add_object(vec![]);
+        document.add_object(vec![]);
+        document.add_object(
+            b"<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>"
+                .to_vec(),
+        );
+        document
+    }
+
+    fn add_object(&mut self, body: Vec<u8>) -> usize {
+        self.objects.push(body);
+        self.objects.len()
+    }
+
+    /// Set the title stored in the document's metadata
+    pub fn title<S>(&mut self, title: S)
+    where
+        S: Into<String>,
+    {
+        self.title = Some(title.into());
+    }
+
+    /// Add a page showing the scene, returning its object number
+    pub fn add_page(&mut self, scene: &Scene) -> usize {
+        let (content, opacities) = content_stream(scene);
+        let compressed = miniz_oxide::deflate::compress_to_vec_zlib(content.as_bytes(), 6);
+        let mut stream = format!(
+            "<< /Length {} /Filter /FlateDecode >>\nstream\n",
+            compressed.len()
+        )
+        .into_bytes();
+        stream.extend(compressed);
+        stream.extend(b"\nendstream");
+        let content_id = self.add_object(stream);
+
+        let mut graphics_states = String::new();
+        for (name, (fill, stroke)) in &opacities {
+            write!(
+                graphics_states,
+                " /{} << /ca {} /CA {} >>",
+                name,
+                number(*fill),
+                number(*stroke)
+            )
+            .unwrap();
+        }
+        let dictionary = format!(
+            "/Type /Page /Parent {} 0 R /MediaBox [0 0 {} {}] \
+             /Resources << /Font << /F1 {} 0 R >> /ExtGState <<{} >> >> /Contents {} 0 R",
+            PAGES,
+            number(scene.width),
+            number(scene.height),
+            FONT,
+            graphics_states,
+            content_id
+        );
+        let id = self.add_object(vec![]);
+        self.pages.push(PageEntry {
+            id,
+            dictionary,
+            annotations: vec![],
+        });
+        id
+    }
+
+    /// Make a rectangle on one page into a link to another page.
+    ///
+    /// The rectangle is `(left, bottom, right, top)` in PDF coordinates,
+    /// which are measured up from the bottom of the page.
+    pub fn add_link(&mut self, from: usize, rect: (f64, f64, f64, f64), to: usize) {
+        let annotation = format!(
+            "<< /Type /Annot /Subtype /Link /Rect [{} {} {} {}] /Border [0 0 0] /Dest [{} 0 R /Fit] >>",
+            number(rect.0),
+            number(rect.1),
+            number(rect.2),
+            number(rect.3),
+            to
+        );
+        let id = self.add_object(annotation.into_bytes());
+        if let Some(page) = self.pages.iter_mut().find(|p| p.id == from) {
+            page.annotations.push(id);
+        }
+    }
+
+    /// Add a bookmark to the document's outline which jumps to a page
+    pub fn add_outline<S>(&mut self, title: S, page: usize)
+    where
+        S: Into<String>,
+    {
+        self.outline.push((title.into(), page));
+    }
+
+    /// Write the outline, returning the object number of its root
+    fn write_outline(&mut self) -> Option<usize> {
+        if self.outline.is_empty() {
+            return None;
+        }
+        let root = self.add_object(vec![]);
+        let first = self.objects.len() + 1;
+        let count = self.outline.len();
+        let entries = std::mem::take(&mut self.outline);
+        for (i, (title, page)) in entries.iter().enumerate() {
+            let id = first + i;
+            let mut entry = format!(
+                "<< /Title ({}) /Parent {} 0 R /Dest [{} 0 R /Fit]",
+                escape(title),
+                root,
+                page
+            );
+            if i > 0 {
+                write!(entry, " /Prev {} 0 R", id - 1).unwrap();
+            }
+            if i + 1 < count {
+                write!(entry, " /Next {} 0 R", id + 1).unwrap();
+            }
+            entry.push_str(" >>");
+            self.add_object(entry.into_bytes());
+        }
+        self.objects[root - 1] = format!(
+            "<< /Type /Outlines /First {} 0 R /Last {} 0 R /Count {} >>",
+            first,
+            first + count - 1,
+            count
+        )
+        .into_bytes();
+        Some(root)
+    }
+
+    /// Write out the finished document
+    pub fn finish(mut self) -> Vec<u8> {
+        for page in &self.pages {
+            let annotations = if page.annotations.is_empty() {
+                String::new()
+            } else {
+                let references: Vec<String> = page
+                    .annotations
+                    .iter()
+                    .map(|a| format!("{} 0 R", a))
+                    .collect();
+                format!(" /Annots [{}]", references.join(" "))
+            };
+            self.objects[page.id - 1] =
+                format!("<< {}{} >>", page.dictionary, annotations).into_bytes();
+        }
+
+        let kids: Vec<String> = self.pages.iter().map(|p| format!("{} 0 R", p.id)).collect();
+        self.objects[PAGES - 1] = format!(
+            "<< /Type /Pages /Kids [{}] /Count {} >>",
+            kids.join(" "),
+            self.pages.len()
+        )
+        .into_bytes();
+
+        self.objects[CATALOG - 1] = match self.write_outline() {
+            Some(outline) => format!(
+                "<< /Type /Catalog /Pages {} 0 R /Outlines {} 0 R /PageMode /UseOutlines >>",
+                PAGES, outline
+            ),
+            None => format!("<< /Type /Catalog /Pages {} 0 R >>", PAGES),
+        }
+        .into_bytes();
+
+        let mut info = String::from("<< /Producer (plotlib)");
+        if let Some(title) = &self.title {
+            write!(info, " /Title ({})", escape(title)).unwrap();
+        }
+        info.push_str(" >>");
+        let info_id = self.add_object(info.into_bytes());
+
+        let mut pdf = b"%PDF-1.4\n%\xE2\xE3\xCF\xD3\n".to_vec();
+        let mut offsets = vec![];
+        for (i, body) in self.objects.iter().enumerate() {
+            offsets.push(pdf.len());
+            pdf.extend(format!("{} 0 obj\n", i + 1).into_bytes());
+            pdf.extend(body);
+            pdf.extend(b"\nendobj\n");
+        }
+
+        let xref_offset = pdf.len();
+        let mut xref = format!("xref\n0 {}\n0000000000 65535 f \n", self.objects.len() + 1);
+        for offset in offsets {
+            writeln!(xref, "{:010} 00000 n ", offset).unwrap();
+        }
+        write!(
+            xref,
+            "trailer\n<< /Size {} /Root {} 0 R /Info {} 0 R >>\nstartxref\n{}\n%%EOF\n",
+            self.objects.len() + 1,
+            CATALOG,
+            info_id,
+            xref_offset
+        )
+        .unwrap();
+        pdf.extend(xref.into_bytes());
+        pdf
+    }
+}
+
+/// Render a scene as a single-page PDF document
+pub fn render(scene: &Scene) -> Vec<u8> {
+    let mut document = Document::new();
+    document.add_page(scene);
+    document.finish()
+}
+
+/// Format a number compactly, as PDF doesn't accept exponents
+fn number(value: f64) -> String {
+    let rounded = (value * 1000.).round() / 1000.;
+    if rounded == 0. {
+        return "0".into();
+    }
+    let s = format!("{:.3}", rounded);
+    s.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+fn colour(Rgb(r, g, b): Rgb) -> String {
+    format!(
+        "{} {} {}",
+        number(f64::from(r) / 255.),
+        number(f64::from(g) / 255.),
+        number(f64::from(b) / 255.)
+    )
+}
+
+/// Encode text as a PDF string literal in `WinAnsiEncoding`
+fn escape(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            '\\' | '(' | ')' => format!("\\{}", c),
+            ' '..='~' => c.to_string(),
+            // WinAnsiEncoding agrees with Latin-1 for the upper half
+            '\u{A0}'..='\u{FF}' => format!("\\{:03o}", c as u32),
+            _ => "?".into(),
+        })
+        .collect()
+}
+
+/// The width of some text in Helvetica, in units of the font size
+pub fn text_width(text: &str) -> f64 {
+    text.chars()
+        .map(|c| match c {
+            ' '..='~' => HELVETICA_WIDTHS[c as usize - ' ' as usize],
+            _ => 556,
+        })
+        .map(f64::from)
+        .sum::<f64>()
+        / 1000.
+}
+
+/// Build the content stream for a page,
+/// along with the named opacities it refers to as `(fill, stroke)` pairs
+fn content_stream(scene: &Scene) -> (String, BTreeMap<String, (f64, f64)>) {
+    let mut opacities = BTreeMap::new();
+    let mut content = String::new();
+    // Flip the page so that the scene's coordinates can be used directly
+    writeln!(content, "1 0 0 -1 0 {} cm", number(scene.height)).unwrap();
+    for item in &scene.items {
+        content.push_str("q\n");
+        if let Some((left, top, right, bottom)) = item.clip {
+            writeln!(
+                content,
+                "{} {} {} {} re W n",
+                number(left),
+                number(top),
+                number(right - left),
+                number(bottom - top)
+            )
+            .unwrap();
+        }
+        write_item(&mut content, &mut opacities, item);
+        content.push_str("Q\n");
+    }
+    (content, opacities)
+}
+
+fn write_item(content: &mut String, opacities: &mut BTreeMap<String, (f64, f64)>, item: &Item) {
+    let fill_opacity = item.fill.as_ref().map_or(1., |f| f.opacity);
+    let stroke_opacity = item.stroke.as_ref().map_or(1., |s| s.opacity);
+    if fill_opacity < 1. || stroke_opacity < 1. {
+        let name = format!(
+            "GS{}_{}",
+            (fill_opacity * 1000.).round(),
+            (stroke_opacity * 1000.).round()
+        );
+        writeln!(content, "/{} gs", name).unwrap();
+        opacities.insert(name, (fill_opacity, stroke_opacity));
+    }
+    if let Some(fill) = &item.fill {
+        writeln!(content, "{} rg", colour(fill.colour)).unwrap();
+    }
+    if let Some(stroke) = &item.stroke {
+        writeln!(
+            content,
+            "{} RG {} w {} j",
+            colour(stroke.colour),
+            number(stroke.width),
+            match stroke.join {
+                Join::Miter => 0,
+                Join::Round => 1,
+                Join::Bevel => 2,
+            }
+        )
+        .unwrap();
+    }
+
+    let painting = match (&item.fill, &item.stroke) {
+        (Some(_), Some(_)) => "B",
+        (Some(_), None) => "f",
+        (None, Some(_)) => "S",
+        (None, None) => return,
+    };
+
+    match &item.shape {
+        Shape::Path(subpaths) => {
+            // Open paths are never filled in plotlib's output, but PDF would fill them
+            let painting = if subpaths.iter().all(|s| !s.closed) {
+                match item.stroke {
+                    Some(_) => "S",
+                    None => return,
+                }
+            } else {
+                painting
+            };
+            for subpath in subpaths {
+                for (i, &(x, y)) in subpath.points.iter().enumerate() {
+                    let operator = if i == 0 { "m" } else { "l" };
+                    writeln!(content, "{} {} {}", number(x), number(y), operator).unwrap();
+                }
+                if subpath.closed {
+                    content.push_str("h\n");
+                }
+            }
+            writeln!(content, "{}", painting).unwrap();
+        }
+        Shape::Circle((cx, cy), r) => {
+            let k = r * KAPPA;
+            writeln!(content, "{} {} m", number(cx + r), number(*cy)).unwrap();
+            for &(x1, y1, x2, y2, x3, y3) in &[
+                (cx + r, cy + k, cx + k, cy + r, *cx, cy + r),
+                (cx - k, cy + r, cx - r, cy + k, cx - r, *cy),
+                (cx - r, cy - k, cx - k, cy - r, *cx, cy - r),
+                (cx + k, cy - r, cx + r, cy - k, cx + r, *cy),
+            ] {
+                writeln!(
+                    content,
+                    "{} {} {} {} {} {} c",
+                    number(x1),
+                    number(y1),
+                    number(x2),
+                    number(y2),
+                    number(x3),
+                    number(y3)
+                )
+                .unwrap();
+            }
+            writeln!(content, "h {}", painting).unwrap();
+        }
+        Shape::Text(text) => {
+            if item.fill.is_none() {
+                return;
+            }
+            let width = text_width(&text.content) * text.font_size;
+            let x = match text.anchor {
+                Anchor::Start => text.x,
+                Anchor::Middle => text.x - width / 2.,
+                Anchor::End => text.x - width,
+            };
+            // Helvetica's capitals are 0.718 of the font size tall
+            let y = if text.centred {
+                text.y + 0.359 * text.font_size
+            } else {
+                text.y
+            };
+            // Text space has y pointing up, but the page has been flipped
+            let m = text.transform.then(&Transform {
+                a: 1.,
+                b: 0.,
+                c: 0.,
+                d: -1.,
+                e: x,
+                f: y,
+            });
+            writeln!(
+                content,
+                "BT /F1 {} Tf {} {} {} {} {} {} Tm ({}) Tj ET",
+                number(text.font_size),
+                number(m.a),
+                number(m.b),
+                number(m.c),
+                number(m.d),
+                number(m.e),
+                number(m.f),
+                escape(&text.content)
+            )
+            .unwrap();
+        }
+    }
+}
+
+/// The advance widths of the printable ASCII characters in Helvetica, per 1000 units
+const HELVETICA_WIDTHS: [u16; 95] = [
+    278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278,
+    278, // ' ' to '/'
+    556, 556, 556, 556, 556, 556, 556, 556, 556, 556, // '0' to '9'
+    278, 278, 584, 584, 584, 556, 1015, // ':' to '@'
+    667, 667, 722, 722, 667, 611, 778, 722, 278, 500, 667, 556, 833, // 'A' to 'M'
+    722, 778, 667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, // 'N' to 'Z'
+    278, 278, 278, 469, 556, 333, // '[' to '`'
+    556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500, 222, 833, // 'a' to 'm'
+    556, 556, 556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, // 'n' to 'z'
+    334, 260, 334, 584, // '{' to '~'
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_number() {
+        assert_eq!(number(0.), "0");
+        assert_eq!(number(-0.0001), "0");
+        assert_eq!(number(12.), "12");
+        assert_eq!(number(-3.25), "-3.25");
+        assert_eq!(number(1.0 / 3.0), "0.333");
+        assert_eq!(number(1e-12), "0");
+    }
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(escape("a (b) \\c"), "a \\(b\\) \\\\c");
+        assert_eq!(escape("5µs"), "5\\265s");
+        assert_eq!(escape("→"), "?");
+    }
+
+    #[test]
+    fn test_text_width() {
+        assert_eq!(text_width(""), 0.);
+        assert_eq!(text_width("10"), 1.112);
+        assert_eq!(text_width("Wi"), 1.166);
+    }
+
+    #[test]
+    fn test_document_structure() {
+        let scene = Scene {
+            width: 100.,
+            height: 50.,
+            items: vec![],
+        };
+        let pdf = render(&scene);
+        let find = |needle: &str| {
+            pdf.windows(needle.len())
+                .position(|w| w == needle.as_bytes())
+                .unwrap_or_else(|| panic!("{} not found", needle))
+        };
+        // Read a number written as ASCII at the given offset
+        let read_number = |offset: usize| -> usize {
+            let digits: Vec<u8> = pdf[offset..]
+                .iter()
+                .cloned()
+                .take_while(u8::is_ascii_digit)
+                .collect();
+            String::from_utf8(digits).unwrap().parse().unwrap()
+        };
+        assert!(pdf.starts_with(b"%PDF-1.4"));
+        find("/MediaBox [0 0 100 50]");
+        find("/Count 1");
+        assert!(pdf.ends_with(b"%%EOF\n"));
+
+        // The cross-reference table should point at each object
+        let xref = read_number(find("startxref\n") + "startxref\n".len());
+        assert!(pdf[xref..].starts_with(b"xref\n0 "));
+        let first_object = read_number(xref + "xref\n0 5\n0000000000 65535 f \n".len());
+        assert!(pdf[first_object..].starts_with(b"1 0 obj"));
+    }
+}
@@ -0,0 +1,587 @@
+/*!
+A module for rasterising pages into PNG images.
+
+Rather than knowing how to draw each representation,
+this works from the `scene::Scene` flattened out of the page's SVG document,
+so anything `svg_render` can draw will also appear in the PNG.
+Everything is drawn with a small anti-aliased scanline rasteriser
+and a built-in bitmap font, so no system libraries are needed.
+*/
+
+use std::f64::consts::PI;
+
+use failure::format_err;
+
+use crate::colour::Rgb;
+use crate::errors::Result;
+use crate::scene::{Anchor, Join, Rect, Scene, Shape, Stroke, SubPath, Text};
+
+/// The number of sub-scanlines sampled per row of pixels
+const SUBSAMPLES: usize = 4;
+
+/// SVG's default limit on the ratio of miter length to stroke width
+const MITER_LIMIT: f64 = 4.0;
+
+type Polygon = Vec<(f64, f64)>;
+
+/// An RGB image which can have polygons painted onto it
+pub struct Canvas {
+    width: usize,
+    height: usize,
+    pixels: Vec<[f64; 3]>,
+}
+
+impl Canvas {
+    /// Create a canvas filled with white
+    pub fn new(width: usize, height: usize) -> Canvas {
+        Canvas {
+            width,
+            height,
+            pixels: vec![[1., 1., 1.]; width * height],
+        }
+    }
+
+    /// Paint the union of some polygons in a single colour.
+    ///
+    /// Overlapping polygons are only painted once,
+    /// so strokes built from many pieces don't darken where the pieces meet.
+    /// Nothing is painted outside of the clip rectangle, if there is one.
+    fn fill(&mut self, polygons: &[Polygon], colour: Rgb, opacity: f64, clip: Option<Rect>) {
+        let (clip_left, clip_top, clip_right, clip_bottom) = clip.unwrap_or((
+            f64::NEG_INFINITY,
+            f64::NEG_INFINITY,
+            f64::INFINITY,
+            f64::INFINITY,
+        ));
+        // Make all polygons wind the same way so that the non-zero rule gives their union
+        let edges: Vec<((f64, f64), (f64, f64))> = polygons
+            .iter()
+            .filter(|p| p.len() > 2)
+            .flat_map(|p| {
+                let reverse = signed_area(p) < 0.;
+                let n = p.len();
+                (0..n).map(move |i| {
+                    let (a, b) = (p[i], p[(i + 1) % n]);
+                    if reverse {
+                        (b, a)
+                    } else {
+                        (a, b)
+                    }
+                })
+            })
+            .filter(|(a, b)| a.1 != b.1)
+            .collect();
+        if edges.is_empty() {
+            return;
+        }
+
+        let y_min = edges
+            .iter()
+            .map(|(a, b)| a.1.min(b.1))
+            .fold(f64::INFINITY, f64::min);
+        let y_max = edges
+            .iter()
+            .map(|(a, b)| a.1.max(b.1))
+            .fold(f64::NEG_INFINITY, f64::max);
+        let row_start = y_min.max(clip_top).floor().max(0.) as usize;
+        let row_end = (y_max.min(clip_bottom).ceil().max(0.) as usize).min(self.height);
+
+        let (r, g, b) = colour.to_unit();
+        let mut coverage = vec![0f64; self.width];
+        let mut crossings: Vec<(f64, i32)> = vec![];
+
+        for row in row_start..row_end {
+            coverage.iter_mut().for_each(|c| *c = 0.);
+            for sub in 0..SUBSAMPLES {
+                let y = row as f64 + (sub as f64 + 0.5) / SUBSAMPLES as f64;
+                if y < clip_top || y >= clip_bottom {
+                    continue;
+                }
+                crossings.clear();
+                for &((x0, y0), (x1, y1)) in &edges {
+                    let (low, high, direction) = if y0 < y1 { (y0, y1, 1) } else { (y1, y0, -1) };
+                    if y >= low && y < high {
+                        let x = x0 + (y - y0) * (x1 - x0) / (y1 - y0);
+                        crossings.push((x, direction));
+                    }
+                }
+                crossings.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+
+                let mut winding = 0;
+                let mut span_start = 0.;
+                for &(x, direction) in &crossings {
+                    if winding == 0 {
+                        span_start = x;
+                    }
+                    winding += direction;
+                    if winding == 0 {
+                        let (start, end) = (span_start.max(clip_left), x.min(clip_right));
+                        if start < end {
+                            add_span(&mut coverage, start, end, 1. / SUBSAMPLES as f64);
+                        }
+                    }
+                }
+            }
+            for (column, &c) in coverage.iter().enumerate() {
+                if c > 0. {
+                    let alpha = c.min(1.) * opacity;
+                    let pixel = &mut self.pixels[row * self.width + column];
+                    pixel[0] += (r - pixel[0]) * alpha;
+                    pixel[1] += (g - pixel[1]) * alpha;
+                    pixel[2] += (b - pixel[2]) * alpha;
+                }
+            }
+        }
+    }
+
+    /// Paint all the items of a scene
+    pub fn draw(&mut self, scene: &Scene) {
+        for item in &scene.items {
+            match &item.shape {
+                Shape::Path(subpaths) => {
+                    if let Some(fill) = &item.fill {
+                        let polygons: Vec<Polygon> =
+                            subpaths.iter().map(|s| s.points.clone()).collect();
+                        self.fill(&polygons, fill.colour, fill.opacity, item.clip);
+                    }
+                    if let Some(stroke) = &item.stroke {
+                        let polygons: Vec<Polygon> = subpaths
+                            .iter()
+                            .flat_map(|s| stroke_polygons(s, stroke))
+                            .collect();
+                        self.fill(&polygons, stroke.colour, stroke.opacity, item.clip);
+                    }
+                }
+                Shape::Circle(centre, radius) => {
+                    if let Some(fill) = &item.fill {
+                        self.fill(
+                            &[circle(*centre, *radius)],
+                            fill.colour,
+                            fill.opacity,
+                            item.clip,
+                        );
+                    }
+                    if let Some(stroke) = &item.stroke {
+                        let outline = SubPath {
+                            points: circle(*centre, *radius),
+                            closed: true,
+                        };
+                        self.fill(
+                            &stroke_polygons(&outline, stroke),
+                            stroke.colour,
+                            stroke.opacity,
+                            item.clip,
+                        );
+                    }
+                }
+                Shape::Text(text) => {
+                    if let Some(fill) = &item.fill {
+                        self.fill(&text_polygons(text), fill.colour, fill.opacity, item.clip);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Encode the canvas as a PNG file
+    pub fn to_png(&self) -> Result<Vec<u8>> {
+        if self.width == 0 || self.height == 0 {
+            return Err(format_err!("Cannot create an empty PNG"));
+        }
+        let mut raw = Vec::with_capacity((self.width * 3 + 1) * self.height);
+        for row in self.pixels.chunks(self.width) {
+            raw.push(0); // no filtering
+            for pixel in row {
+                raw.extend(
+                    pixel
+                        .iter()
+                        .map(|&c| (c * 255.).round().clamp(0., 255.) as u8),
+                );
+            }
+        }
+
+        let mut header = vec![];
+        header.extend(&(self.width as u32).to_be_bytes());
+        header.extend(&(self.height as u32).to_be_bytes());
+        header.extend(&[8, 2, 0, 0, 0]); // 8-bit RGB, default compression, no interlacing
+
+        let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+        write_chunk(&mut png, b"IHDR", &header);
+        write_chunk(
+            &mut png,
+            b"IDAT",
+            &miniz_oxide::deflate::compress_to_vec_zlib(&raw, 6),
+        );
+        write_chunk(&mut png, b"IEND", &[]);
+        Ok(png)
+    }
+}
+
+/// Render a scene to the bytes of a PNG image the same size as the scene
+pub fn render(scene: &Scene) -> Result<Vec<u8>> {
+    let mut canvas = Canvas::new(scene.width.ceil() as usize, scene.height.ceil() as usize);
+    canvas.draw(scene);
+    canvas.to_png()
+}
+
+/// Add `weight` to the coverage of the cells between `start` and `end`, including partial cells
+fn add_span(coverage: &mut [f64], start: f64, end: f64, weight: f64) {
+    let width = coverage.len() as f64;
+    let (start, end) = (start.max(0.).min(width), end.max(0.).min(width));
+    if end <= start {
+        return;
+    }
+    let (first, last) = (start.floor() as usize, end.floor() as usize);
+    if first == last {
+        coverage[first] += (end - start) * weight;
+        return;
+    }
+    coverage[first] += (first as f64 + 1. - start) * weight;
+    for c in &mut coverage[first + 1..last] {
+        *c += weight;
+    }
+    if last < coverage.len() {
+        coverage[last] += (end - last as f64) * weight;
+    }
+}
+
+fn signed_area(polygon: &[(f64, f64)]) -> f64 {
+    let n = polygon.len();
+    (0..n)
+        .map(|i| {
+            let (a, b) = (polygon[i], polygon[(i + 1) % n]);
+            a.0 * b.1 - b.0 * a.1
+        })
+        .sum::<f64>()
+        / 2.
+}
+
+fn circle(centre: (f64, f64), radius: f64) -> Polygon {
+    let segments = (radius * 2.).clamp(16., 128.) as usize;
+    (0..segments)
+        .map(|i| {
+            let angle = 2. * PI * i as f64 / segments as f64;
+            (
+                centre.0 + radius * angle.cos(),
+                centre.1 + radius * angle.sin(),
+            )
+        })
+        .collect()
+}
+
+/// Break the outline of a stroked sub-path into polygons which together cover it
+fn stroke_polygons(subpath: &SubPath, stroke: &Stroke) -> Vec<Polygon> {
+    let half = stroke.width / 2.;
+    let mut points: Vec<(f64, f64)> = vec![];
+    for &p in &subpath.points {
+        if points.last() != Some(&p) {
+            points.push(p);
+        }
+    }
+    if subpath.closed && points.len() > 2 && points.first() == points.last() {
+        points.pop();
+    }
+    if points.len() < 2 || half <= 0. {
+        return vec![];
+    }
+
+    let segment_count = if subpath.closed {
+        points.len()
+    } else {
+        points.len() - 1
+    };
+    let segments: Vec<((f64, f64), (f64, f64))> = (0..segment_count)
+        .map(|i| (points[i], points[(i + 1) % points.len()]))
+        .collect();
+
+    let direction = |(a, b): ((f64, f64), (f64, f64))| {
+        let length = ((b.0 - a.0).powi(2) + (b.1 - a.1).powi(2)).sqrt();
+        ((b.0 - a.0) / length, (b.1 - a.1) / length)
+    };
+
+    let mut polygons: Vec<Polygon> = segments
+        .iter()
+        .map(|&(a, b)| {
+            let (dx, dy) = direction((a, b));
+            let (nx, ny) = (-dy * half, dx * half);
+            vec![
+                (a.0 + nx, a.1 + ny),
+                (b.0 + nx, b.1 + ny),
+                (b.0 - nx, b.1 - ny),
+                (a.0 - nx, a.1 - ny),
+            ]
+        })
+        .collect();
+
+    // Join each pair of consecutive segments
+    let joins = if subpath.closed {
+        segments.len()
+    } else {
+        segments.len() - 1
+    };
+    for i in 0..joins {
+        let (first, second) = (segments[i], segments[(i + 1) % segments.len()]);
+        let vertex = first.1;
+        match stroke.join {
+            Join::Round => polygons.push(circle(vertex, half)),
+            Join::Miter | Join::Bevel => {
+                let d1 = direction(first);
+                let d2 = direction(second);
+                for &side in &[1., -1.] {
+                    let a = (vertex.0 - d1.1 * half * side, vertex.1 + d1.0 * half * side);
+                    let b = (vertex.0 - d2.1 * half * side, vertex.1 + d2.0 * half * side);
+                    let mut corner = vec![vertex, a, b];
+                    let cross = d1.0 * d2.1 - d1.1 * d2.0;
+                    if stroke.join == Join::Miter && cross.abs() > 1e-9 {
+                        // Where the outside edges of the two segments meet
+                        let t = ((b.0 - a.0) * d2.1 - (b.1 - a.1) * d2.0) / cross;
+                        let tip = (a.0 + d1.0 * t, a.1 + d1.1 * t);
+                        let length =
+                            ((tip.0 - vertex.0).powi(2) + (tip.1 - vertex.1).powi(2)).sqrt();
+                        if t >= 0. && length <= MITER_LIMIT * half {
+                            corner = vec![vertex, a, tip, b];
+                        }
+                    }
+                    polygons.push(corner);
+                }
+            }
+        }
+    }
+    polygons
+}
+
+/// Lay out a piece of text in the bitmap font, returning one square for each lit dot
+fn text_polygons(text: &Text) -> Vec<Polygon> {
+    let dot = text.font_size / 9.;
+    let chars: Vec<char> = text.content.chars().collect();
+    let width = if chars.is_empty() {
+        0.
+    } else {
+        (chars.len() * GLYPH_ADVANCE - 1) as f64 * dot
+    };
+    let left = match text.anchor {
+        Anchor::Start => text.x,
+        Anchor::Middle => text.x - width / 2.,
+        Anchor::End => text.x - width,
+    };
+    let top = if text.centred {
+        text.y - GLYPH_HEIGHT as f64 * dot / 2.
+    } else {
+        text.y - GLYPH_HEIGHT as f64 * dot
+    };
+
+    let mut polygons = vec![];
+    for (i, &c) in chars.iter().enumerate() {
+        let x = left + (i * GLYPH_ADVANCE) as f64 * dot;
+        for (row, bits) in glyph(c).iter().enumerate() {
+            let y = top + row as f64 * dot;
+            for column in 0..5 {
+                if bits & (0x10 >> column) != 0 {
+                    let x = x + column as f64 * dot;
+                    polygons.push(
+                        [(x, y), (x + dot, y), (x + dot, y + dot), (x, y + dot)]
+                            .iter()
+                            .map(|&p| text.transform.apply(p))
+                            .collect(),
+                    );
+                }
+            }
+        }
+    }
+    polygons
+}
+
+fn write_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend(&(data.len() as u32).to_be_bytes());
+    let start = png.len();
+    png.extend(kind);
+    png.extend(data);
+    let crc = crc32(&png[start..]);
+    png.extend(&crc.to_be_bytes());
+}
+
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = 0xFFFF_FFFFu32;
+    for &byte in bytes {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+/// The width of a glyph plus its spacing, in dots
+const GLYPH_ADVANCE: usize = 6;
+/// The height of a glyph, in dots
+const GLYPH_HEIGHT: usize = 7;
+
+/// The rows of a glyph, top to bottom, with the leftmost of the five columns in bit 4
+fn glyph(c: char) -> [u8; GLYPH_HEIGHT] {
+    match c {
+        ' '..='~' => FONT[c as usize - ' ' as usize],
+        // Written by `TickFormat::SI` for millionths
+        'µ' => [0x00, 0x00, 0x11, 0x11, 0x13, 0x1D, 0x10],
+        _ => FONT['?' as usize - ' ' as usize],
+    }
+}
+
+/// A 5×7 font covering printable ASCII
+const FONT: [[u8; GLYPH_HEIGHT]; 95] = [
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], // ' '
+    [0x04, 0x04, 0x04, 0x04, 0x04, 0x00, 0x04], // !
+    [0x0A, 0x0A, 0x0A, 0x00, 0x00, 0x00, 0x00], // "
+    [0x0A, 0x0A, 0x1F, 0x0A, 0x1F, 0x0A, 0x0A], // #
+    [0x04, 0x0F, 0x14, 0x0E, 0x05, 0x1E, 0x04], // $
+    [0x18, 0x19, 0x02, 0x04, 0x08, 0x13, 0x03], // %
+    [0x0C, 0x12, 0x14, 0x08, 0x15, 0x12, 0x0D], // &
+    [0x04, 0x04, 0x08, 0x00, 0x00, 0x00, 0x00], // '
+    [0x02, 0x04, 0x08, 0x08, 0x08, 0x04, 0x02], // (
+    [0x08, 0x04, 0x02, 0x02, 0x02, 0x04, 0x08], // )
+    [0x00, 0x04, 0x15, 0x0E, 0x15, 0x04, 0x00], // *
+    [0x00, 0x04, 0x04, 0x1F, 0x04, 0x04, 0x00], // +
+    [0x00, 0x00, 0x00, 0x00, 0x0C, 0x04, 0x08], // ,
+    [0x00, 0x00, 0x00, 0x1F, 0x00, 0x00, 0x00], // -
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x0C, 0x0C], // .
+    [0x00, 0x01, 0x02, 0x04, 0x08, 0x10, 0x00], // /
+    [0x0E, 0x11, 0x13, 0x15, 0x19, 0x11, 0x0E], // 0
+    [0x04, 0x0C, 0x04, 0x04, 0x04, 0x04, 0x0E], // 1
+    [0x0E, 0x11, 0x01, 0x02, 0x04, 0x08, 0x1F], // 2
+    [0x1F, 0x02, 0x04, 0x02, 0x01, 0x11, 0x0E], // 3
+    [0x02, 0x06, 0x0A, 0x12, 0x1F, 0x02, 0x02], // 4
+    [0x1F, 0x10, 0x1E, 0x01, 0x01, 0x11, 0x0E], // 5
+    [0x06, 0x08, 0x10, 0x1E, 0x11, 0x11, 0x0E], // 6
+    [0x1F, 0x01, 0x02, 0x04, 0x08, 0x08, 0x08], // 7
+    [0x0E, 0x11, 0x11, 0x0E, 0x11, 0x11, 0x0E], // 8
+    [0x0E, 0x11, 0x11, 0x0F, 0x01, 0x02, 0x0C], // 9
+    [0x00, 0x0C, 0x0C, 0x00, 0x0C, 0x0C, 0x00], // :
+    [0x00, 0x0C, 0x0C, 0x00, 0x0C, 0x04, 0x08], // ;
+    [0x02, 0x04, 0x08, 0x10, 0x08, 0x04, 0x02], // <
+    [0x00, 0x00, 0x1F, 0x00, 0x1F, 0x00, 0x00], // =
+    [0x08, 0x04, 0x02, 0x01, 0x02, 0x04, 0x08], // >
+    [0x0E, 0x11, 0x01, 0x02, 0x04, 0x00, 0x04], // ?
+    [0x0E, 0x11, 0x01, 0x0D, 0x15, 0x15, 0x0E], // @
+    [0x0E, 0x11, 0x11, 0x11, 0x1F, 0x11, 0x11], // A
+    [0x1E, 0x11, 0x11, 0x1E, 0x11, 0x11, 0x1E], // B
+    [0x0E, 0x11, 0x10, 0x10, 0x10, 0x11, 0x0E], // C
+    [0x1C, 0x12, 0x11, 0x11, 0x11, 0x12, 0x1C], // D
+    [0x1F, 0x10, 0x10, 0x1E, 0x10, 0x10, 0x1F], // E
+    [0x1F, 0x10, 0x10, 0x1E, 0x10, 0x10, 0x10], // F
+    [0x0E, 0x11, 0x10, 0x17, 0x11, 0x11, 0x0F], // G
+    [0x11, 0x11, 0x11, 0x1F, 0x11, 0x11, 0x11], // H
+    [0x0E, 0x04, 0x04, 0x04, 0x04, 0x04, 0x0E], // I
+    [0x07, 0x02, 0x02, 0x02, 0x02, 0x12, 0x0C], // J
+    [0x11, 0x12, 0x14, 0x18, 0x14, 0x12, 0x11], // K
+    [0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x1F], // L
+    [0x11, 0x1B, 0x15, 0x15, 0x11, 0x11, 0x11], // M
+    [0x11, 0x11, 0x19, 0x15, 0x13, 0x11, 0x11], // N
+    [0x0E, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0E], // O
+    [0x1E, 0x11, 0x11, 0x1E, 0x10, 0x10, 0x10], // P
+    [0x0E, 0x11, 0x11, 0x11, 0x15, 0x12, 0x0D], // Q
+    [0x1E, 0x11, 0x11, 0x1E, 0x14, 0x12, 0x11], // R
+    [0x0F, 0x10, 0x10, 0x0E, 0x01, 0x01, 0x1E], // S
+    [0x1F, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04], // T
+    [0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0E], // U
+    [0x11, 0x11, 0x11, 0x11, 0x11, 0x0A, 0x04], // V
+    [0x11, 0x11, 0x11, 0x15, 0x15, 0x15, 0x0A], // W
+    [0x11, 0x11, 0x0A, 0x04, 0x0A, 0x11, 0x11], // X
+    [0x11, 0x11, 0x11, 0x0A, 0x04, 0x04, 0x04], // Y
+    [0x1F, 0x01, 0x02, 0x04, 0x08, 0x10, 0x1F], // Z
+    [0x0E, 0x08, 0x08, 0x08, 0x08, 0x08, 0x0E], // [
+    [0x00, 0x10, 0x08, 0x04, 0x02, 0x01, 0x00], // \
+    [0x0E, 0x02, 0x02, 0x02, 0x02, 0x02, 0x0E], // ]
+    [0x04, 0x0A, 0x11, 0x00, 0x00, 0x00, 0x00], // ^
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x1F], // _
+    [0x08, 0x04, 0x02, 0x00, 0x00, 0x00, 0x00], // `
+    [0x00, 0x00, 0x0E, 0x01, 0x0F, 0x11, 0x0F], // a
+    [0x10, 0x10, 0x16, 0x19, 0x11, 0x11, 0x1E], // b
+    [0x00, 0x00, 0x0E, 0x10, 0x10, 0x11, 0x0E], // c
+    [0x01, 0x01, 0x0D, 0x13, 0x11, 0x11, 0x0F], // d
+    [0x00, 0x00, 0x0E, 0x11, 0x1F, 0x10, 0x0E], // e
+    [0x06, 0x09, 0x08, 0x1C, 0x08, 0x08, 0x08], // f
+    [0x00, 0x0F, 0x11, 0x11, 0x0F, 0x01, 0x0E], // g
+    [0x10, 0x10, 0x16, 0x19, 0x11, 0x11, 0x11], // h
+    [0x04, 0x00, 0x0C, 0x04, 0x04, 0x04, 0x0E], // i
+    [0x02, 0x00, 0x06, 0x02, 0x02, 0x12, 0x0C], // j
+    [0x10, 0x10, 0x12, 0x14, 0x18, 0x14, 0x12], // k
+    [0x0C, 0x04, 0x04, 0x04, 0x04, 0x04, 0x0E], // l
+    [0x00, 0x00, 0x1A, 0x15, 0x15, 0x11, 0x11], // m
+    [0x00, 0x00, 0x16, 0x19, 0x11, 0x11, 0x11], // n
+    [0x00, 0x00, 0x0E, 0x11, 0x11, 0x11, 0x0E], // o
+    [0x00, 0x00, 0x1E, 0x11, 0x1E, 0x10, 0x10], // p
+    [0x00, 0x00, 0x0D, 0x13, 0x0F, 0x01, 0x01], // q
+    [0x00, 0x00, 0x16, 0x19, 0x10, 0x10, 0x10], // r
+    [0x00, 0x00, 0x0E, 0x10, 0x0E, 0x01, 0x1E], // s
+    [0x08, 0x08, 0x1C, 0x08, 0x08, 0x09, 0x06], // t
+    [0x00, 0x00, 0x11, 0x11, 0x11, 0x13, 0x0D], // u
+    [0x00, 0x00, 0x11, 0x11, 0x11, 0x0A, 0x04], // v
+    [0x00, 0x00, 0x11, 0x11, 0x15, 0x15, 0x0A], // w
+    [0x00, 0x00, 0x11, 0x0A, 0x04, 0x0A, 0x11], // x
+    [0x00, 0x00, 0x11, 0x11, 0x0F, 0x01, 0x0E], // y
+    [0x00, 0x00, 0x1F, 0x02, 0x04, 0x08, 0x1F], // z
+    [0x02, 0x04, 0x04, 0x08, 0x04, 0x04, 0x02], // {
+    [0x04, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04], // |
+    [0x08, 0x04, 0x04, 0x02, 0x04, 0x04, 0x08], // }
+    [0x00, 0x00, 0x08, 0x15, 0x02, 0x00, 0x00], // ~
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_span() {
+        let mut coverage = vec![0.; 4];
+        add_span(&mut coverage, 0.5, 2.25, 1.);
+        assert_eq!(coverage, [0.5, 1., 0.25, 0.]);
+
+        let mut coverage = vec![0.; 4];
+        add_span(&mut coverage, -3., 1.5, 0.5);
+        add_span(&mut coverage, 3.5, 9., 0.5);
+        assert_eq!(coverage, [0.5, 0.25, 0., 0.25]);
+    }
+
+    #[test]
+    fn test_fill_union() {
+        let mut canvas = Canvas::new(4, 1);
+        let square = vec![(0., 0.), (2., 0.), (2., 1.), (0., 1.)];
+        // Overlapping and oppositely wound, but still painted once
+        let other = vec![(1., 1.), (3., 1.), (3., 0.), (1., 0.)];
+        canvas.fill(&[square, other], Rgb(0, 0, 0), 0.5, None);
+        let greys: Vec<f64> = canvas.pixels.iter().map(|p| p[0]).collect();
+        assert_eq!(greys, [0.5, 0.5, 0.5, 1.]);
+    }
+
+    #[test]
+    fn test_fill_clip() {
+        let mut canvas = Canvas::new(4, 2);
+        let square = vec![(0., 0.), (4., 0.), (4., 2.), (0., 2.)];
+        canvas.fill(&[square], Rgb(0, 0, 0), 1., Some((0.5, 1., 2., 5.)));
+        let greys: Vec<f64> = canvas.pixels.iter().map(|p| p[0]).collect();
+        assert_eq!(greys, [1., 1., 1., 1., 0.5, 0., 1., 1.]);
+    }
+
+    #[test]
+    fn test_glyph() {
+        assert_eq!(glyph('é'), glyph('?'));
+        assert_ne!(glyph('µ'), glyph('?'));
+    }
+
+    #[test]
+    fn test_crc32() {
+        assert_eq!(crc32(b"IEND"), 0xAE42_6082);
+    }
+
+    #[test]
+    fn test_png_header() {
+        let png = Canvas::new(3, 2).to_png().unwrap();
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+        assert_eq!(&png[12..16], b"IHDR");
+        assert_eq!(&png[16..24], &[0, 0, 0, 3, 0, 0, 0, 2]);
+        assert!(Canvas::new(0, 2).to_png().is_err());
+    }
+}
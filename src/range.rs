@@ -0,0 +1,251 @@
+#![deny(missing_docs)]
+
+//! Configure how the range of an axis is chosen from the data.
+//!
+//! When no range is set on a view, each axis is fitted to the data it shows
+//! according to a `RangePolicy`.
+//! The policies can be combined: the range is first stretched to include zero,
+//! then padded, then widened to the nearest ticks.
+//!
+//! # Examples
+//!
+//! ```rust
+//! # use plotlib::repr::Plot;
+//! # use plotlib::view::ContinuousView;
+//! use plotlib::range::RangePolicy;
+//!
+//! let p = Plot::new(vec![(1.3, 12.), (8.7, 27.)]);
+//! let v = ContinuousView::new()
+//!     .add(p)
+//!     .x_range_policy(RangePolicy::exact().padding(0.05))
+//!     .y_range_policy(RangePolicy::exact().include_zero().nice());
+//! ```
+
+use crate::axis;
+use crate::scale::Scale;
+use crate::ticks::{self, TickAlgorithm};
+
+/// How the range of an axis is fitted to the data
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct RangePolicy {
+    include_zero: bool,
+    padding: f64,
+    nice: bool,
+}
+
+impl RangePolicy {
+    /// Fit the range exactly to the smallest and largest values
+    pub fn exact() -> Self {
+        RangePolicy::default()
+    }
+
+    /// Stretch the range so that it includes zero
+    ///
+    /// This is ignored on logarithmic axes.
+    pub fn include_zero(mut self) -> Self {
+        self.include_zero = true;
+        self
+    }
+
+    /// Pad each end of the range by a fraction of its length, e.g. `0.05` for 5%
+    ///
+    /// An end of the range at zero is never padded, so that bars and areas stay on the axis.
+    pub fn padding(mut self, fraction: f64) -> Self {
+        self.padding = fraction;
+        self
+    }
+
+    /// Widen the range to the nearest ticks outside it
+    ///
+    /// Logarithmic axes are widened to the nearest powers of ten or of _e_,
+    /// and symmetric logarithmic axes to the nearest powers of ten outside the linear region.
+    pub fn nice(mut self) -> Self {
+        self.nice = true;
+        self
+    }
+
+    /// Fit a range to data running from `lower` to `upper`
+    pub(crate) fn apply(
+        &self,
+        lower: f64,
+        upper: f64,
+        scale: Scale,
+        max_ticks: usize,
+        algorithm: TickAlgorithm,
+    ) -> (f64, f64) {
+        let (mut lower, mut upper) = (lower, upper);
+        if !axis::Range::new(lower, upper).is_valid() {
+            return (lower, upper);
+        }
+
+        if self.include_zero && scale.is_valid_range(lower.min(0.), upper.max(0.)) {
+            lower = lower.min(0.);
+            upper = upper.max(0.);
+        }
+
+        if self.padding != 0. {
+            // Pad along the axis, so that both ends of a logarithmic axis are padded equally
+            let (start, end) = (scale.transform(lower), scale.transform(upper));
+            let pad = (end - start) * self.padding;
+            if lower != 0. {
+                lower = scale.inverse(start - pad);
+            }
+            if upper != 0. {
+                upper = scale.inverse(end + pad);
+            }
+        }
+
+        if self.nice {
+            let (nice_lower, nice_upper) = match (scale, algorithm) {
+                (Scale::Log10, _) => (
+                    10f64.powf(lower.log10().floor()),
+                    10f64.powf(upper.log10().ceil()),
+                ),
+                (Scale::Ln, _) => (natural_below(lower), natural_above(upper)),
+                (Scale::SymLog(threshold), _) => (
+                    symlog_below(lower, threshold),
+                    -symlog_below(-upper, threshold),
+                ),
+                (Scale::Linear, TickAlgorithm::Extended { .. })
+                | (Scale::Power(_), TickAlgorithm::Extended { .. }) => {
+                    match ticks::calculate_extended_ticks(lower, upper, max_ticks, true) {
+                        Some((lower, upper, _)) => (lower, upper),
+                        None => (lower, upper),
+                    }
+                }
+                (Scale::Linear, TickAlgorithm::Steps) | (Scale::Power(_), TickAlgorithm::Steps) => {
+                    let step = axis::calculate_tick_step_for_range(lower, upper, max_ticks);
+                    // Remove floating-point errors from the multiples of the step,
+                    // keeping as many decimal places as the step has so that small ranges survive
+                    let places = (-step.log10().floor()).max(0.) as usize;
+                    let fix = |x: f64| format!("{:.*}", places, x).parse().unwrap_or(x);
+                    (
+                        fix((lower / step).floor() * step),
+                        fix((upper / step).ceil() * step),
+                    )
+                }
+            };
+            lower = nice_lower;
+            upper = nice_upper;
+        }
+
+        (lower, upper)
+    }
+}
+
+/// The greatest power of _e_ at most `x`, as it is labelled on an axis
+fn natural_below(x: f64) -> f64 {
+    let exponent = x.ln().floor() as i32;
+    let power = axis::natural(1, exponent);
+    // The label is rounded, so may lie just above the value
+    if power > x {
+        axis::natural(1, exponent - 1)
+    } else {
+        power
+    }
+}
+
+/// The least power of _e_ at least `x`, as it is labelled on an axis
+fn natural_above(x: f64) -> f64 {
+    let exponent = x.ln().ceil() as i32;
+    let power = axis::natural(1, exponent);
+    if power < x {
+        axis::natural(1, exponent + 1)
+    } else {
+        power
+    }
+}
+
+/// The greatest tick of a symmetric logarithmic axis at most `x`,
+/// where the ticks are zero and the powers of ten either side of it from the threshold out
+fn symlog_below(x: f64, threshold: f64) -> f64 {
+    let threshold = 10f64.powf(threshold.log10().ceil());
+    if x >= threshold {
+        10f64.powf(x.log10().floor())
+    } else if x >= 0. {
+        0.
+    } else if -x <= threshold {
+        -threshold
+    } else {
+        -10f64.powf((-x).log10().ceil())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn apply(policy: RangePolicy, lower: f64, upper: f64) -> (f64, f64) {
+        policy.apply(lower, upper, Scale::Linear, 6, TickAlgorithm::Steps)
+    }
+
+    #[test]
+    fn test_policies() {
+        assert_eq!(apply(RangePolicy::exact(), 1.3, 8.7), (1.3, 8.7));
+        assert_eq!(
+            apply(RangePolicy::exact().include_zero(), 1.3, 8.7),
+            (0., 8.7)
+        );
+        assert_eq!(
+            apply(RangePolicy::exact().include_zero(), -8.7, -1.3),
+            (-8.7, 0.)
+        );
+        assert_eq!(apply(RangePolicy::exact().padding(0.1), 0., 10.), (0., 11.));
+        assert_eq!(apply(RangePolicy::exact().padding(0.1), -5., 5.), (-6., 6.));
+        assert_eq!(apply(RangePolicy::exact().nice(), 1.3, 8.7), (0., 10.));
+        assert_eq!(
+            apply(RangePolicy::exact().nice(), -7.93, 15.58),
+            (-10., 20.)
+        );
+    }
+
+    #[test]
+    fn test_small_nice_range() {
+        // Both ends are kept apart rather than rounded to zero
+        let (lower, upper) = apply(RangePolicy::exact().nice(), 1e-7, 5e-7);
+        assert!(lower <= 1e-7 && 5e-7 <= upper && lower < upper);
+        assert_eq!(apply(RangePolicy::exact().nice(), 0.13, 0.27), (0.12, 0.28));
+    }
+
+    #[test]
+    fn test_scaled_policies() {
+        let policy = RangePolicy::exact().include_zero().padding(0.5).nice();
+        let (lower, upper) = policy.apply(2., 50., Scale::Log10, 6, TickAlgorithm::Steps);
+        assert_eq!((lower, upper), (0.1, 1000.));
+        let (lower, upper) =
+            RangePolicy::exact()
+                .nice()
+                .apply(2., 50., Scale::Ln, 6, TickAlgorithm::Steps);
+        assert_eq!((lower, upper), (1., 54.6));
+        // Rounded labels which lie just inside the data are stepped past
+        let (lower, upper) =
+            RangePolicy::exact()
+                .nice()
+                .apply(20.086, 20.087, Scale::Ln, 6, TickAlgorithm::Steps);
+        assert_eq!((lower, upper), (7.389, 54.6));
+        let (lower, upper) = RangePolicy::exact().nice().apply(
+            -30.,
+            0.5,
+            Scale::SymLog(1.),
+            6,
+            TickAlgorithm::Steps,
+        );
+        assert_eq!((lower, upper), (-100., 1.));
+        let (lower, upper) = RangePolicy::exact().nice().apply(
+            0.5,
+            250.,
+            Scale::SymLog(2.),
+            6,
+            TickAlgorithm::Steps,
+        );
+        assert_eq!((lower, upper), (0., 1000.));
+        let (lower, upper) = RangePolicy::exact().nice().apply(
+            0.13,
+            8.7,
+            Scale::Linear,
+            6,
+            TickAlgorithm::Extended { expand: false },
+        );
+        assert_eq!((lower, upper), (0., 10.));
+    }
+}
@@ -0,0 +1,289 @@
+/*!
+The `report` module provides a way of collecting many pages into one PDF document.
+
+Each page of a report keeps its own dimensions and is given a title,
+which is drawn above it and added to the document's bookmarks.
+
+```no_run
+# use plotlib::page::Page;
+# use plotlib::report::Report;
+# use plotlib::repr::Plot;
+# use plotlib::view::ContinuousView;
+let v = ContinuousView::new().add(Plot::new(vec![(0., 1.), (1., 3.)]));
+Report::new()
+    .title("Nightly results")
+    .add_page("Throughput", Page::single(&v))
+    .add_page("Latency", Page::single(&v).dimensions(800, 400))
+    .page_numbers(true)
+    .table_of_contents(true)
+    .save("nightly.pdf")
+    .unwrap();
+```
+*/
+
+use std::path::Path;
+
+use failure::format_err;
+use failure::ResultExt;
+
+use crate::colour::Rgb;
+use crate::errors::Result;
+use crate::page::Page;
+use crate::pdf_render::Document;
+use crate::scene::{Anchor, Fill, Item, Scene, Shape, Text, Transform};
+
+/// The height of the band above each page which holds its title
+const TITLE_HEIGHT: f64 = 30.;
+/// The height of the band below each page which holds its number
+const FOOTER_HEIGHT: f64 = 20.;
+/// The vertical distance between entries in the table of contents
+const ENTRY_HEIGHT: f64 = 20.;
+const MARGIN: f64 = 40.;
+
+/**
+A collection of titled pages which is written as a single PDF document
+*/
+pub struct Report<'a> {
+    title: Option<String>,
+    pages: Vec<(String, Page<'a>)>,
+    page_numbers: bool,
+    table_of_contents: bool,
+}
+
+impl<'a> Default for Report<'a> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> Report<'a> {
+    /**
+    Creates an empty report for pages to be added to
+    */
+    pub fn new() -> Self {
+        Report {
+            title: None,
+            pages: Vec::new(),
+            page_numbers: false,
+            table_of_contents: false,
+        }
+    }
+
+    /// Set the title of the document, which also heads the table of contents
+    pub fn title<T>(mut self, title: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Add a page to the end of the report, with a title drawn above it
+    pub fn add_page<T>(mut self, title: T, page: Page<'a>) -> Self
+    where
+        T: Into<String>,
+    {
+        self.pages.push((title.into(), page));
+        self
+    }
+
+    /// Whether to number each page at its foot
+    pub fn page_numbers(mut self, page_numbers: bool) -> Self {
+        self.page_numbers = page_numbers;
+        self
+    }
+
+    /// Whether to begin the report with a page listing the titles of the others
+    pub fn table_of_contents(mut self, table_of_contents: bool) -> Self {
+        self.table_of_contents = table_of_contents;
+        self
+    }
+
+    /**
+    Render the report to the bytes of a PDF document
+    */
+    pub fn to_pdf(&self) -> Result<Vec<u8>> {
+        if self.pages.is_empty() {
+            return Err(format_err!("Cannot create a report with no pages"));
+        }
+
+        let mut scenes = vec![];
+        for (title, page) in &self.pages {
+            let mut scene = Scene::from_document(&page.to_svg()?)?;
+            scene.translate(0., TITLE_HEIGHT);
+            scene.height += TITLE_HEIGHT;
+            if self.page_numbers {
+                scene.height += FOOTER_HEIGHT;
+            }
+            let centre = scene.width / 2.;
+            scene
+                .items
+                .push(text(title, centre, 20., 16., Anchor::Middle));
+            scenes.push(scene);
+        }
+
+        let contents = if self.table_of_contents {
+            Some(self.contents_page(&scenes))
+        } else {
+            None
+        };
+        let first_number = if contents.is_some() { 2 } else { 1 };
+        let total = scenes.len() + first_number - 1;
+
+        let mut document = Document::new();
+        if let Some(title) = &self.title {
+            document.title(title.as_str());
+        }
+        let contents = contents.map(|mut scene| {
+            if self.page_numbers {
+                number_page(&mut scene, 1, total);
+            }
+            (document.add_page(&scene), scene.width, scene.height)
+        });
+        let mut ids = vec![];
+        for (i, mut scene) in scenes.into_iter().enumerate() {
+            if self.page_numbers {
+                number_page(&mut scene, i + first_number, total);
+            }
+            ids.push(document.add_page(&scene));
+        }
+
+        if let Some((contents_id, width, height)) = contents {
+            // Make each entry a link to its page
+            for (i, &id) in ids.iter().enumerate() {
+                let baseline = entry_baseline(i);
+                let rect = (
+                    MARGIN - 5.,
+                    height - baseline - 6.,
+                    width - MARGIN + 5.,
+                    height - baseline + 14.,
+                );
+                document.add_link(contents_id, rect, id);
+            }
+            document.add_outline("Contents", contents_id);
+        }
+        for ((title, _), &id) in self.pages.iter().zip(&ids) {
+            document.add_outline(title.as_str(), id);
+        }
+        Ok(document.finish())
+    }
+
+    /**
+    Save the report to a file as a PDF document
+    */
+    pub fn save<P>(&self, path: P) -> Result<()>
+    where
+        P: AsRef<Path>,
+    {
+        std::fs::write(path, self.to_pdf()?)
+            .context("saving pdf")
+            .map_err(From::from)
+    }
+
+    /**
+    Build the page listing the title and number of every other page
+
+    It is as wide as the widest page and at least as tall as the first.
+    */
+    fn contents_page(&self, scenes: &[Scene]) -> Scene {
+        let width = scenes.iter().map(|s| s.width).fold(0., f64::max);
+        let mut height = entry_baseline(scenes.len()) + ENTRY_HEIGHT;
+        if self.page_numbers {
+            height += FOOTER_HEIGHT;
+        }
+        let height = f64::max(height, scenes[0].height);
+        let mut items = vec![text(
+            self.title.as_deref().unwrap_or("Contents"),
+            width / 2.,
+            2. * MARGIN - 20.,
+            16.,
+            Anchor::Middle,
+        )];
+        for (i, (title, _)) in self.pages.iter().enumerate() {
+            let baseline = entry_baseline(i);
+            items.push(text(title, MARGIN, baseline, 12., Anchor::Start));
+            items.push(text(
+                &(i + 2).to_string(),
+                width - MARGIN,
+                baseline,
+                12.,
+                Anchor::End,
+            ));
+        }
+        Scene {
+            width,
+            height,
+            items,
+        }
+    }
+}
+
+/// The baseline of the `n`th entry in the table of contents, measured down the page
+fn entry_baseline(n: usize) -> f64 {
+    2. * MARGIN + 10. + ENTRY_HEIGHT * n as f64
+}
+
+/// Add "Page n of N" to the foot of a scene
+fn number_page(scene: &mut Scene, number: usize, total: usize) {
+    let label = format!("Page {} of {}", number, total);
+    let (x, y) = (scene.width / 2., scene.height - 7.);
+    scene.items.push(text(&label, x, y, 10., Anchor::Middle));
+}
+
+fn text(content: &str, x: f64, y: f64, font_size: f64, anchor: Anchor) -> Item {
+    Item {
+        shape: Shape::Text(Text {
+            content: content.into(),
+            transform: Transform::identity(),
+            x,
+            y,
+            font_size,
+            anchor,
+            centred: false,
+        }),
+        fill: Some(Fill {
+            colour: Rgb(0, 0, 0),
+            opacity: 1.,
+        }),
+        stroke: None,
+        clip: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repr::Plot;
+    use crate::view::ContinuousView;
+
+    fn contains(pdf: &[u8], needle: &str) -> bool {
+        pdf.windows(needle.len()).any(|w| w == needle.as_bytes())
+    }
+
+    #[test]
+    fn test_empty_report() {
+        assert!(Report::new().to_pdf().is_err());
+    }
+
+    #[test]
+    fn test_report_pages() {
+        let v = ContinuousView::new().add(Plot::new(vec![(0., 1.), (1., 2.)]));
+        let pdf = Report::new()
+            .title("Summary")
+            .add_page("First", Page::single(&v))
+            .add_page("Second", Page::single(&v).dimensions(300, 200))
+            .page_numbers(true)
+            .table_of_contents(true)
+            .to_pdf()
+            .unwrap();
+
+        assert!(contains(&pdf, "/Count 3"));
+        // Each page is extended by the title and footer bands
+        assert!(contains(&pdf, "/MediaBox [0 0 600 450]"));
+        assert!(contains(&pdf, "/MediaBox [0 0 300 250]"));
+        assert!(contains(&pdf, "/Title (Summary)"));
+        assert!(contains(&pdf, "/Title (Second)"));
+        assert!(contains(&pdf, "/PageMode /UseOutlines"));
+        assert_eq!(pdf.windows(11).filter(|w| w == b"/Subtype /L").count(), 2);
+    }
+}
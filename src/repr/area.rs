@@ -0,0 +1,241 @@
+//! Plot filled areas
+
+//! # Examples
+
+//! ```
+//! # use plotlib::repr::Area;
+//! # use plotlib::style::AreaStyle;
+//! # use plotlib::view::ContinuousView;
+//! // A band between a lower and an upper bound
+//! let upper = vec![(0., 2.), (1., 2.5), (2., 3.1), (3., 2.8)];
+//! let lower = vec![(0., 1.), (1., 1.2), (2., 1.9), (3., 1.5)];
+//! let a = Area::between(upper, lower).style(AreaStyle::new().fill("teal").opacity(0.4));
+//! let v = ContinuousView::new().add(a);
+//! ```
+
+use std::f64;
+
+use svg;
+use svg::node;
+use svg::Node;
+
+use crate::axis;
+use crate::braille_render;
+use crate::repr::ContinuousRepresentation;
+use crate::style::*;
+use crate::svg_render;
+use crate::text_render;
+
+/// Representation of the region between a series and a horizontal baseline,
+/// or between two series, filled in
+#[derive(Debug, Clone)]
+pub struct Area {
+    pub data: Vec<(f64, f64)>,
+    /// The series bounding the other side of the region, or None to fill down to the baseline
+    pub lower: Option<Vec<(f64, f64)>>,
+    pub baseline: f64,
+    pub style: AreaStyle,
+    /// None if the edges of the region should not be drawn
+    pub line_style: Option<LineStyle>,
+    pub legend: Option<String>,
+}
+
+impl Area {
+    /// Fill between the series and a baseline, which is at zero unless it is moved with `baseline`
+    pub fn new(data: Vec<(f64, f64)>) -> Self {
+        Area {
+            data,
+            lower: None,
+            baseline: 0.,
+            style: AreaStyle::new(),
+            line_style: None,
+            legend: None,
+        }
+    }
+
+    /// Fill between two series, which need not share their x values
+    pub fn between(upper: Vec<(f64, f64)>, lower: Vec<(f64, f64)>) -> Self {
+        Area {
+            lower: Some(lower),
+            ..Area::new(upper)
+        }
+    }
+
+    pub fn baseline(mut self, value: f64) -> Self {
+        self.baseline = value;
+        self
+    }
+    pub fn style(mut self, other: AreaStyle) -> Self {
+        self.style.overlay(&other);
+        self
+    }
+    /// Draw the edges of the region along the series, but not along the baseline
+    pub fn line_style(mut self, other: LineStyle) -> Self {
+        if let Some(ref mut self_style) = self.line_style {
+            self_style.overlay(&other);
+        } else {
+            self.line_style = Some(other);
+        }
+        self
+    }
+    pub fn legend(mut self, legend: String) -> Self {
+        self.legend = Some(legend);
+        self
+    }
+
+    /// The lower edge of the region, running below the series from its first point to its last
+    fn lower_edge(&self) -> Vec<(f64, f64)> {
+        match &self.lower {
+            Some(lower) => lower.clone(),
+            None => match (self.data.first(), self.data.last()) {
+                (Some(&(first, _)), Some(&(last, _))) => {
+                    vec![(first, self.baseline), (last, self.baseline)]
+                }
+                _ => vec![],
+            },
+        }
+    }
+
+    /// The edges of the region which are drawn with the line style
+    fn edges(&self) -> Vec<&[(f64, f64)]> {
+        let mut edges = vec![&self.data[..]];
+        if let Some(lower) = &self.lower {
+            edges.push(lower);
+        }
+        edges.retain(|edge| !edge.is_empty());
+        edges
+    }
+
+    fn range_of(&self, value: fn(&(f64, f64)) -> f64) -> (f64, f64) {
+        let mut min = f64::INFINITY;
+        let mut max = f64::NEG_INFINITY;
+        for v in self.data.iter().chain(&self.lower_edge()).map(value) {
+            min = min.min(v);
+            max = max.max(v);
+        }
+        (min, max)
+    }
+}
+
+impl ContinuousRepresentation for Area {
+    fn range(&self, dim: u32) -> (f64, f64) {
+        match dim {
+            0 => self.range_of(|&(x, _)| x),
+            1 => self.range_of(|&(_, y)| y),
+            _ => panic!("Axis out of range"),
+        }
+    }
+
+    fn to_svg(
+        &self,
+        x_axis: &axis::ContinuousAxis,
+        y_axis: &axis::ContinuousAxis,
+        face_width: f64,
+        face_height: f64,
+    ) -> svg::node::element::Group {
+        let mut group = node::element::Group::new();
+        group.append(svg_render::draw_face_area(
+            &self.data,
+            &self.lower_edge(),
+            x_axis,
+            y_axis,
+            face_width,
+            face_height,
+            &self.style,
+        ));
+        if let Some(ref line_style) = self.line_style {
+            for edge in self.edges() {
+                group.append(svg_render::draw_face_line(
+                    edge,
+                    x_axis,
+                    y_axis,
+                    face_width,
+                    face_height,
+                    line_style,
+                ))
+            }
+        }
+        group
+    }
+
+    fn legend_svg(&self) -> Option<svg::node::element::Group> {
+        self.legend.as_ref().map(|legend| {
+            svg_render::draw_area_legend(legend, &self.style, self.line_style.as_ref())
+        })
+    }
+
+    fn to_text(
+        &self,
+        x_axis: &axis::ContinuousAxis,
+        y_axis: &axis::ContinuousAxis,
+        face_width: u32,
+        face_height: u32,
+    ) -> String {
+        let layers = self.text_layers(x_axis, y_axis, face_width, face_height);
+        text_render::flatten_layers(&layers, face_width, face_height)
+    }
+
+    fn text_layers(
+        &self,
+        x_axis: &axis::ContinuousAxis,
+        y_axis: &axis::ContinuousAxis,
+        face_width: u32,
+        face_height: u32,
+    ) -> Vec<(String, String)> {
+        let face_area = text_render::render_face_area(
+            &self.data,
+            &self.lower_edge(),
+            x_axis,
+            y_axis,
+            face_width,
+            face_height,
+            '░',
+        );
+        let mut layers = vec![(face_area, self.style.get_fill())];
+        if let Some(line_style) = &self.line_style {
+            for edge in self.edges() {
+                let face_lines =
+                    text_render::render_face_line(edge, x_axis, y_axis, face_width, face_height);
+                layers.push((face_lines, line_style.get_colour()));
+            }
+        }
+        layers
+    }
+
+    fn to_braille(
+        &self,
+        x_axis: &axis::ContinuousAxis,
+        y_axis: &axis::ContinuousAxis,
+        face_width: u32,
+        face_height: u32,
+    ) -> String {
+        let layers = self.braille_layers(x_axis, y_axis, face_width, face_height);
+        text_render::flatten_layers(&layers, face_width, face_height)
+    }
+
+    fn braille_layers(
+        &self,
+        x_axis: &axis::ContinuousAxis,
+        y_axis: &axis::ContinuousAxis,
+        face_width: u32,
+        face_height: u32,
+    ) -> Vec<(String, String)> {
+        let face_area = braille_render::render_face_area(
+            &self.data,
+            &self.lower_edge(),
+            x_axis,
+            y_axis,
+            face_width,
+            face_height,
+        );
+        let mut layers = vec![(face_area, self.style.get_fill())];
+        if let Some(line_style) = &self.line_style {
+            for edge in self.edges() {
+                let face_lines =
+                    braille_render::render_face_line(edge, x_axis, y_axis, face_width, face_height);
+                layers.push((face_lines, line_style.get_colour()));
+            }
+        }
+        layers
+    }
+}
@@ -21,6 +21,7 @@ use crate::axis;
 use crate::repr::CategoricalRepresentation;
 use crate::style::BoxStyle;
 use crate::svg_render;
+use crate::text_render;
 use crate::utils;
 
 enum BoxData<'a> {
@@ -118,11 +119,29 @@ impl<'a> CategoricalRepresentation for BoxPlot<'a> {
 
     fn to_text(
         &self,
-        _x_axis: &axis::CategoricalAxis,
-        _y_axis: &axis::ContinuousAxis,
-        _face_width: u32,
-        _face_height: u32,
+        x_axis: &axis::CategoricalAxis,
+        y_axis: &axis::ContinuousAxis,
+        face_width: u32,
+        face_height: u32,
     ) -> String {
-        "".into()
+        text_render::render_face_box(
+            self.get_data(),
+            &self.label,
+            x_axis,
+            y_axis,
+            face_width,
+            face_height,
+        )
+    }
+
+    fn text_layers(
+        &self,
+        x_axis: &axis::CategoricalAxis,
+        y_axis: &axis::ContinuousAxis,
+        face_width: u32,
+        face_height: u32,
+    ) -> Vec<(String, String)> {
+        let face = self.to_text(x_axis, y_axis, face_width, face_height);
+        vec![(face, self.style.get_fill())]
     }
 }
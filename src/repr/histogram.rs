@@ -20,11 +20,10 @@ TODO:
     - What should be the default?
 */
 
-use std;
-
 use svg;
 
 use crate::axis;
+use crate::braille_render;
 use crate::repr::ContinuousRepresentation;
 use crate::style::BoxStyle;
 use crate::svg_render;
@@ -60,7 +59,7 @@ impl Histogram {
         let mut max = v.iter().fold(-1. / 0., |a, &b| f64::max(a, b));
         let mut min = v.iter().fold(1. / 0., |a, &b| f64::min(a, b));
 
-        if (min - max).abs() < std::f64::EPSILON {
+        if (min - max).abs() < f64::EPSILON {
             min -= 0.5;
             max += 0.5;
         }
@@ -178,6 +177,38 @@ impl ContinuousRepresentation for Histogram {
     ) -> String {
         text_render::render_face_bars(self, x_axis, y_axis, face_width, face_height)
     }
+
+    fn to_braille(
+        &self,
+        x_axis: &axis::ContinuousAxis,
+        y_axis: &axis::ContinuousAxis,
+        face_width: u32,
+        face_height: u32,
+    ) -> String {
+        braille_render::render_face_bars(self, x_axis, y_axis, face_width, face_height)
+    }
+
+    fn text_layers(
+        &self,
+        x_axis: &axis::ContinuousAxis,
+        y_axis: &axis::ContinuousAxis,
+        face_width: u32,
+        face_height: u32,
+    ) -> Vec<(String, String)> {
+        let face = self.to_text(x_axis, y_axis, face_width, face_height);
+        vec![(face, self.style.get_fill())]
+    }
+
+    fn braille_layers(
+        &self,
+        x_axis: &axis::ContinuousAxis,
+        y_axis: &axis::ContinuousAxis,
+        face_width: u32,
+        face_height: u32,
+    ) -> Vec<(String, String)> {
+        let face = self.to_braille(x_axis, y_axis, face_width, face_height);
+        vec![(face, self.style.get_fill())]
+    }
 }
 
 #[cfg(test)]
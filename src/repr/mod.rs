@@ -14,14 +14,18 @@ These points may then be layered with other SVG elements from other representati
 
 use crate::axis;
 
+mod area;
 mod barchart;
 mod boxplot;
 mod histogram;
 mod plot;
+mod stackedarea;
+pub use area::*;
 pub use barchart::*;
 pub use boxplot::*;
 pub use histogram::*;
 pub use plot::*;
+pub use stackedarea::*;
 
 /**
 A representation of data that is continuous in two dimensions.
@@ -30,6 +34,11 @@ pub trait ContinuousRepresentation {
     /// The maximum range in each dimension. Used for auto-scaling axes.
     fn range(&self, dim: u32) -> (f64, f64);
 
+    /// The individual data points, if any. Used for marking those outside the axes.
+    fn points(&self) -> &[(f64, f64)] {
+        &[]
+    }
+
     fn to_svg(
         &self,
         x_axis: &axis::ContinuousAxis,
@@ -41,6 +50,12 @@ pub trait ContinuousRepresentation {
     /// Returns None if no legend has been specified for this representation
     fn legend_svg(&self) -> Option<svg::node::element::Group>;
 
+    /// The entries this representation adds to the legend, from the top down.
+    /// Most have at most the one from `legend_svg`.
+    fn legend_entries(&self) -> Vec<svg::node::element::Group> {
+        self.legend_svg().into_iter().collect()
+    }
+
     fn to_text(
         &self,
         x_axis: &axis::ContinuousAxis,
@@ -48,6 +63,43 @@ pub trait ContinuousRepresentation {
         face_width: u32,
         face_height: u32,
     ) -> String;
+
+    /// Like `to_text` but drawn with braille patterns of 2×4 dots per character.
+    /// Falls back to `to_text` for representations which don't support it.
+    fn to_braille(
+        &self,
+        x_axis: &axis::ContinuousAxis,
+        y_axis: &axis::ContinuousAxis,
+        face_width: u32,
+        face_height: u32,
+    ) -> String {
+        self.to_text(x_axis, y_axis, face_width, face_height)
+    }
+
+    /// The face from `to_text` split into layers from the bottom up,
+    /// each with the CSS colour that its characters should be drawn in
+    fn text_layers(
+        &self,
+        x_axis: &axis::ContinuousAxis,
+        y_axis: &axis::ContinuousAxis,
+        face_width: u32,
+        face_height: u32,
+    ) -> Vec<(String, String)> {
+        let face = self.to_text(x_axis, y_axis, face_width, face_height);
+        vec![(face, String::new())]
+    }
+
+    /// The face from `to_braille` split into coloured layers like `text_layers`
+    fn braille_layers(
+        &self,
+        x_axis: &axis::ContinuousAxis,
+        y_axis: &axis::ContinuousAxis,
+        face_width: u32,
+        face_height: u32,
+    ) -> Vec<(String, String)> {
+        let face = self.to_braille(x_axis, y_axis, face_width, face_height);
+        vec![(face, String::new())]
+    }
 }
 
 /**
@@ -75,4 +127,17 @@ pub trait CategoricalRepresentation {
         face_width: u32,
         face_height: u32,
     ) -> String;
+
+    /// The face from `to_text` split into coloured layers
+    /// like `ContinuousRepresentation::text_layers`
+    fn text_layers(
+        &self,
+        x_axis: &axis::CategoricalAxis,
+        y_axis: &axis::ContinuousAxis,
+        face_width: u32,
+        face_height: u32,
+    ) -> Vec<(String, String)> {
+        let face = self.to_text(x_axis, y_axis, face_width, face_height);
+        vec![(face, String::new())]
+    }
 }
@@ -17,11 +17,16 @@ use svg::node;
 use svg::Node;
 
 use crate::axis;
+use crate::braille_render;
 use crate::repr::ContinuousRepresentation;
 use crate::style::*;
 use crate::svg_render;
 use crate::text_render;
 
+/// The two ends of an error bar, which share their y value if it is horizontal
+/// and their x value if it is vertical
+pub type ErrorBar = ((f64, f64), (f64, f64));
+
 /// Representation of any plot with points in the XY plane, visualized as points and/or with lines
 /// in-between.
 #[derive(Debug, Clone)]
@@ -31,6 +36,12 @@ pub struct Plot {
     pub line_style: Option<LineStyle>,
     /// None if no points should be displayed
     pub point_style: Option<PointStyle>,
+    /// The distances below and above each x value which its error bar covers
+    pub x_errors: Option<Vec<(f64, f64)>>,
+    /// The distances below and above each y value which its error bar covers
+    pub y_errors: Option<Vec<(f64, f64)>>,
+    /// The style of any error bars, which are drawn in the default style if this is None
+    pub error_style: Option<ErrorBarStyle>,
     pub legend: Option<String>,
 }
 
@@ -40,6 +51,9 @@ impl Plot {
             data,
             line_style: None,
             point_style: None,
+            x_errors: None,
+            y_errors: None,
+            error_style: None,
             legend: None,
         }
     }
@@ -54,6 +68,9 @@ impl Plot {
             data: values,
             line_style: None,
             point_style: None,
+            x_errors: None,
+            y_errors: None,
+            error_style: None,
             legend: None,
         }
     }
@@ -79,6 +96,51 @@ impl Plot {
         self
     }
 
+    /// Draw horizontal error bars reaching the given distance either side of each point, in order
+    pub fn x_errors(self, errors: Vec<f64>) -> Self {
+        self.x_errors_asymmetric(errors.into_iter().map(|e| (e, e)).collect())
+    }
+    /// Draw horizontal error bars reaching the given distances left and right of each point
+    pub fn x_errors_asymmetric(mut self, errors: Vec<(f64, f64)>) -> Self {
+        self.x_errors = Some(errors);
+        self
+    }
+    /// Draw vertical error bars reaching the given distance either side of each point, in order
+    pub fn y_errors(self, errors: Vec<f64>) -> Self {
+        self.y_errors_asymmetric(errors.into_iter().map(|e| (e, e)).collect())
+    }
+    /// Draw vertical error bars reaching the given distances below and above each point
+    pub fn y_errors_asymmetric(mut self, errors: Vec<(f64, f64)>) -> Self {
+        self.y_errors = Some(errors);
+        self
+    }
+    pub fn error_style(mut self, other: ErrorBarStyle) -> Self {
+        if let Some(ref mut self_style) = self.error_style {
+            self_style.overlay(&other);
+        } else {
+            self.error_style = Some(other);
+        }
+        self
+    }
+
+    /// The two ends of each error bar, with the horizontal ones first.
+    /// Bars of no length are left out.
+    fn error_bars(&self) -> Vec<ErrorBar> {
+        let mut bars = vec![];
+        if let Some(x_errors) = &self.x_errors {
+            for (&(x, y), &(below, above)) in self.data.iter().zip(x_errors) {
+                bars.push(((x - below, y), (x + above, y)));
+            }
+        }
+        if let Some(y_errors) = &self.y_errors {
+            for (&(x, y), &(below, above)) in self.data.iter().zip(y_errors) {
+                bars.push(((x, y - below), (x, y + above)));
+            }
+        }
+        bars.retain(|(start, end)| start != end);
+        bars
+    }
+
     fn x_range(&self) -> (f64, f64) {
         let mut min = f64::INFINITY;
         let mut max = f64::NEG_INFINITY;
@@ -86,6 +148,10 @@ impl Plot {
             min = min.min(x);
             max = max.max(x);
         }
+        for ((start, _), (end, _)) in self.error_bars() {
+            min = min.min(start).min(end);
+            max = max.max(start).max(end);
+        }
         (min, max)
     }
 
@@ -96,8 +162,16 @@ impl Plot {
             min = min.min(y);
             max = max.max(y);
         }
+        for ((_, start), (_, end)) in self.error_bars() {
+            min = min.min(start).min(end);
+            max = max.max(start).max(end);
+        }
         (min, max)
     }
+
+    fn has_error_bars(&self) -> bool {
+        self.x_errors.is_some() || self.y_errors.is_some()
+    }
 }
 
 impl ContinuousRepresentation for Plot {
@@ -109,6 +183,10 @@ impl ContinuousRepresentation for Plot {
         }
     }
 
+    fn points(&self) -> &[(f64, f64)] {
+        &self.data
+    }
+
     fn to_svg(
         &self,
         x_axis: &axis::ContinuousAxis,
@@ -117,6 +195,17 @@ impl ContinuousRepresentation for Plot {
         face_height: f64,
     ) -> svg::node::element::Group {
         let mut group = node::element::Group::new();
+        // Error bars are drawn first so that the points sit over them
+        if self.has_error_bars() {
+            group.append(svg_render::draw_face_error_bars(
+                &self.error_bars(),
+                x_axis,
+                y_axis,
+                face_width,
+                face_height,
+                &self.error_style.clone().unwrap_or_default(),
+            ))
+        }
         if let Some(ref line_style) = self.line_style {
             group.append(svg_render::draw_face_line(
                 &self.data,
@@ -179,23 +268,100 @@ impl ContinuousRepresentation for Plot {
         face_width: u32,
         face_height: u32,
     ) -> String {
-        let face_lines = if let Some(line_style) = &self.line_style {
-            unimplemented!("Text rendering does not yet support line plots")
-        } else {
-            text_render::empty_face(face_width, face_height)
-        };
-        let face_points = if let Some(point_style) = &self.point_style {
-            text_render::render_face_points(
+        let layers = self.text_layers(x_axis, y_axis, face_width, face_height);
+        text_render::flatten_layers(&layers, face_width, face_height)
+    }
+
+    fn text_layers(
+        &self,
+        x_axis: &axis::ContinuousAxis,
+        y_axis: &axis::ContinuousAxis,
+        face_width: u32,
+        face_height: u32,
+    ) -> Vec<(String, String)> {
+        let mut layers = vec![];
+        if self.has_error_bars() {
+            let style = self.error_style.clone().unwrap_or_default();
+            let face_bars = text_render::render_face_error_bars(
+                &self.error_bars(),
+                x_axis,
+                y_axis,
+                face_width,
+                face_height,
+                &style,
+            );
+            layers.push((face_bars, style.get_colour()));
+        }
+        if let Some(line_style) = &self.line_style {
+            let face_lines =
+                text_render::render_face_line(&self.data, x_axis, y_axis, face_width, face_height);
+            layers.push((face_lines, line_style.get_colour()));
+        }
+        if let Some(point_style) = &self.point_style {
+            let face_points = text_render::render_face_points(
                 &self.data,
                 x_axis,
                 y_axis,
                 face_width,
                 face_height,
-                &point_style,
-            )
-        } else {
-            text_render::empty_face(face_width, face_height)
-        };
-        text_render::overlay(&face_lines, &face_points, 0, 0)
+                point_style,
+            );
+            layers.push((face_points, point_style.get_colour()));
+        }
+        layers
+    }
+
+    fn to_braille(
+        &self,
+        x_axis: &axis::ContinuousAxis,
+        y_axis: &axis::ContinuousAxis,
+        face_width: u32,
+        face_height: u32,
+    ) -> String {
+        let layers = self.braille_layers(x_axis, y_axis, face_width, face_height);
+        text_render::flatten_layers(&layers, face_width, face_height)
+    }
+
+    fn braille_layers(
+        &self,
+        x_axis: &axis::ContinuousAxis,
+        y_axis: &axis::ContinuousAxis,
+        face_width: u32,
+        face_height: u32,
+    ) -> Vec<(String, String)> {
+        let mut layers = vec![];
+        if self.has_error_bars() {
+            let style = self.error_style.clone().unwrap_or_default();
+            let face_bars = braille_render::render_face_error_bars(
+                &self.error_bars(),
+                x_axis,
+                y_axis,
+                face_width,
+                face_height,
+                &style,
+            );
+            layers.push((face_bars, style.get_colour()));
+        }
+        if let Some(line_style) = &self.line_style {
+            let face_lines = braille_render::render_face_line(
+                &self.data,
+                x_axis,
+                y_axis,
+                face_width,
+                face_height,
+            );
+            layers.push((face_lines, line_style.get_colour()));
+        }
+        if let Some(point_style) = &self.point_style {
+            let face_points = braille_render::render_face_points(
+                &self.data,
+                x_axis,
+                y_axis,
+                face_width,
+                face_height,
+            );
+            layers.push((face_points, point_style.get_colour()));
+        }
+        layers
     }
 }
@@ -0,0 +1,316 @@
+//! Plot stacked area charts
+
+//! # Examples
+
+//! ```
+//! # use plotlib::repr::StackedArea;
+//! # use plotlib::view::ContinuousView;
+//! // Memory used by each service, stacked to show the total
+//! let s = StackedArea::new(vec![0., 1., 2., 3.])
+//!     .add_layer("api", vec![2., 2.5, 3., 2.8])
+//!     .add_layer("db", vec![4., 4.2, 4.1, 4.6])
+//!     .add_layer("cache", vec![1., 1.5, 1.2, 0.9]);
+//! let v = ContinuousView::new().add(s);
+//! ```
+
+use std::f64;
+
+use svg;
+use svg::node;
+use svg::Node;
+
+use crate::axis;
+use crate::braille_render;
+use crate::repr::ContinuousRepresentation;
+use crate::style::*;
+use crate::svg_render;
+use crate::text_render;
+use crate::utils::PairWise;
+
+/// The characters which the layers are shaded with in turn in text output
+const SHADES: [char; 4] = ['░', '▒', '▓', '█'];
+
+/// A single named series of a stacked area
+#[derive(Debug, Clone)]
+pub struct Layer {
+    pub name: String,
+    pub values: Vec<f64>,
+    pub style: AreaStyle,
+}
+
+/// Representation of several series sharing their x values,
+/// each filled in on top of the ones before it
+#[derive(Debug, Clone)]
+pub struct StackedArea {
+    pub x: Vec<f64>,
+    pub layers: Vec<Layer>,
+    /// Whether each layer is shown as a percentage of the total at each x value
+    pub normalised: bool,
+    /// None if the tops of the layers should not be drawn
+    pub line_style: Option<LineStyle>,
+}
+
+impl StackedArea {
+    pub fn new(x: Vec<f64>) -> Self {
+        StackedArea {
+            x,
+            layers: vec![],
+            normalised: false,
+            line_style: None,
+        }
+    }
+
+    /// Stack a series on top of the layers so far, filled with the next colour of the palette.
+    /// It has one value for each x value, with any which are missing taken as zero.
+    pub fn add_layer<T>(self, name: T, values: Vec<f64>) -> Self
+    where
+        T: Into<String>,
+    {
+        self.add_styled_layer(name, values, AreaStyle::new())
+    }
+
+    /// Like `add_layer`, with the style laid over the colour from the palette
+    pub fn add_styled_layer<T>(mut self, name: T, values: Vec<f64>, style: AreaStyle) -> Self
+    where
+        T: Into<String>,
+    {
+        let mut layer_style = AreaStyle::new().fill(PALETTE[self.layers.len() % PALETTE.len()]);
+        layer_style.overlay(&style);
+        self.layers.push(Layer {
+            name: name.into(),
+            values,
+            style: layer_style,
+        });
+        self
+    }
+
+    /// Show each layer as a percentage of the total at each x value, so that the stack fills 0 to 100
+    pub fn normalised(mut self) -> Self {
+        self.normalised = true;
+        self
+    }
+
+    /// Draw the top of each layer
+    pub fn line_style(mut self, other: LineStyle) -> Self {
+        if let Some(ref mut self_style) = self.line_style {
+            self_style.overlay(&other);
+        } else {
+            self.line_style = Some(other);
+        }
+        self
+    }
+
+    /// The top of each layer at each x value, which is the bottom of the next
+    fn stacks(&self) -> Vec<Vec<f64>> {
+        let mut tops = vec![0.; self.x.len()];
+        let mut stacks = vec![];
+        for layer in &self.layers {
+            for (top, value) in tops.iter_mut().zip(&layer.values) {
+                *top += value;
+            }
+            stacks.push(tops.clone());
+        }
+        // The running totals are scaled rather than the values so that the top is exactly 100
+        if self.normalised {
+            for stack in &mut stacks {
+                for (top, &total) in stack.iter_mut().zip(&tops) {
+                    *top = if total == 0. { 0. } else { 100. * *top / total };
+                }
+            }
+        }
+        stacks
+    }
+
+    /// The boundaries between the layers from the baseline up,
+    /// so that each layer fills between one boundary and the next
+    fn boundaries(&self) -> Vec<Vec<(f64, f64)>> {
+        let baseline = vec![0.; self.x.len()];
+        std::iter::once(baseline)
+            .chain(self.stacks())
+            .map(|tops| self.x.iter().cloned().zip(tops).collect())
+            .collect()
+    }
+
+    fn x_range(&self) -> (f64, f64) {
+        let mut min = f64::INFINITY;
+        let mut max = f64::NEG_INFINITY;
+        for &x in &self.x {
+            min = min.min(x);
+            max = max.max(x);
+        }
+        (min, max)
+    }
+
+    /// The range of the stack from the baseline to its highest point
+    fn y_range(&self) -> (f64, f64) {
+        let mut min = 0_f64;
+        let mut max = 0_f64;
+        for &top in self.stacks().iter().flatten() {
+            min = min.min(top);
+            max = max.max(top);
+        }
+        (min, max)
+    }
+}
+
+impl ContinuousRepresentation for StackedArea {
+    fn range(&self, dim: u32) -> (f64, f64) {
+        match dim {
+            0 => self.x_range(),
+            1 => self.y_range(),
+            _ => panic!("Axis out of range"),
+        }
+    }
+
+    fn to_svg(
+        &self,
+        x_axis: &axis::ContinuousAxis,
+        y_axis: &axis::ContinuousAxis,
+        face_width: f64,
+        face_height: f64,
+    ) -> svg::node::element::Group {
+        let mut group = node::element::Group::new();
+        let boundaries = self.boundaries();
+        for ((lower, upper), layer) in boundaries.pairwise().zip(&self.layers) {
+            group.append(svg_render::draw_face_area(
+                upper,
+                lower,
+                x_axis,
+                y_axis,
+                face_width,
+                face_height,
+                &layer.style,
+            ));
+        }
+        // The tops are drawn after all of the fills so that none is covered
+        if let Some(ref line_style) = self.line_style {
+            for upper in boundaries.iter().skip(1).filter(|upper| !upper.is_empty()) {
+                group.append(svg_render::draw_face_line(
+                    upper,
+                    x_axis,
+                    y_axis,
+                    face_width,
+                    face_height,
+                    line_style,
+                ));
+            }
+        }
+        group
+    }
+
+    /// All of the legend entries, stacked in one group
+    fn legend_svg(&self) -> Option<svg::node::element::Group> {
+        let entries = self.legend_entries();
+        if entries.is_empty() {
+            return None;
+        }
+        let mut group = node::element::Group::new();
+        for (i, entry) in entries.into_iter().enumerate() {
+            group.append(entry.set("transform", format!("translate(0, {})", i * 18)));
+        }
+        Some(group)
+    }
+
+    /// One entry for each layer, from the top of the stack down
+    fn legend_entries(&self) -> Vec<svg::node::element::Group> {
+        self.layers
+            .iter()
+            .rev()
+            .map(|layer| {
+                svg_render::draw_area_legend(&layer.name, &layer.style, self.line_style.as_ref())
+            })
+            .collect()
+    }
+
+    fn to_text(
+        &self,
+        x_axis: &axis::ContinuousAxis,
+        y_axis: &axis::ContinuousAxis,
+        face_width: u32,
+        face_height: u32,
+    ) -> String {
+        let layers = self.text_layers(x_axis, y_axis, face_width, face_height);
+        text_render::flatten_layers(&layers, face_width, face_height)
+    }
+
+    fn text_layers(
+        &self,
+        x_axis: &axis::ContinuousAxis,
+        y_axis: &axis::ContinuousAxis,
+        face_width: u32,
+        face_height: u32,
+    ) -> Vec<(String, String)> {
+        let boundaries = self.boundaries();
+        let mut layers = vec![];
+        for (i, ((lower, upper), layer)) in boundaries.pairwise().zip(&self.layers).enumerate() {
+            // Each layer above the first leaves its lower edge to the layer it sits on
+            let render = if i == 0 {
+                text_render::render_face_area
+            } else {
+                text_render::render_face_stacked_layer
+            };
+            let face_area = render(
+                upper,
+                lower,
+                x_axis,
+                y_axis,
+                face_width,
+                face_height,
+                SHADES[i % SHADES.len()],
+            );
+            layers.push((face_area, layer.style.get_fill()));
+        }
+        if let Some(line_style) = &self.line_style {
+            for upper in &boundaries[1..] {
+                let face_lines =
+                    text_render::render_face_line(upper, x_axis, y_axis, face_width, face_height);
+                layers.push((face_lines, line_style.get_colour()));
+            }
+        }
+        layers
+    }
+
+    fn to_braille(
+        &self,
+        x_axis: &axis::ContinuousAxis,
+        y_axis: &axis::ContinuousAxis,
+        face_width: u32,
+        face_height: u32,
+    ) -> String {
+        let layers = self.braille_layers(x_axis, y_axis, face_width, face_height);
+        text_render::flatten_layers(&layers, face_width, face_height)
+    }
+
+    fn braille_layers(
+        &self,
+        x_axis: &axis::ContinuousAxis,
+        y_axis: &axis::ContinuousAxis,
+        face_width: u32,
+        face_height: u32,
+    ) -> Vec<(String, String)> {
+        let boundaries = self.boundaries();
+        let mut layers = vec![];
+        for (i, ((lower, upper), layer)) in boundaries.pairwise().zip(&self.layers).enumerate() {
+            let render = if i == 0 {
+                braille_render::render_face_area
+            } else {
+                braille_render::render_face_stacked_layer
+            };
+            let face_area = render(upper, lower, x_axis, y_axis, face_width, face_height);
+            layers.push((face_area, layer.style.get_fill()));
+        }
+        if let Some(line_style) = &self.line_style {
+            for upper in &boundaries[1..] {
+                let face_lines = braille_render::render_face_line(
+                    upper,
+                    x_axis,
+                    y_axis,
+                    face_width,
+                    face_height,
+                );
+                layers.push((face_lines, line_style.get_colour()));
+            }
+        }
+        layers
+    }
+}
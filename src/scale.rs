@@ -0,0 +1,117 @@
+#![deny(missing_docs)]
+
+//! Configure how values are mapped along a continuous axis.
+//!
+//! By default the axes of a `ContinuousView` are linear.
+//! Data which spans many orders of magnitude can instead be drawn on a logarithmic scale,
+//! which places ticks at each decade with minor ticks between them.
+//!
+//! # Examples
+//!
+//! ```rust
+//! # use plotlib::repr::Plot;
+//! # use plotlib::view::ContinuousView;
+//! use plotlib::scale::Scale;
+//!
+//! let p = Plot::new(vec![(1., 0.02), (10., 0.3), (100., 12.), (1000., 450.)]);
+//! let v = ContinuousView::new()
+//!     .add(p)
+//!     .x_scale(Scale::Log10)
+//!     .y_scale(Scale::SymLog(1.));
+//! ```
+
+use std::f64;
+
+/// The mapping from data values to positions along an axis
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Scale {
+    /// Equal distances for equal differences (the default)
+    #[default]
+    Linear,
+    /// Equal distances for equal ratios, with ticks at powers of ten.
+    /// The range must be positive.
+    Log10,
+    /// Equal distances for equal ratios, with ticks at powers of _e_.
+    /// The range must be positive.
+    Ln,
+    /// Logarithmic in both directions away from zero,
+    /// but linear within the given threshold of it so that zero and negative values can be shown.
+    /// The threshold must be positive.
+    SymLog(f64),
+    /// Positions proportional to the value raised to the given exponent,
+    /// keeping the sign of the value.
+    /// The exponent must be positive.
+    Power(f64),
+}
+
+impl Scale {
+    /// Map a value into the space in which the axis is linear
+    pub(crate) fn transform(self, value: f64) -> f64 {
+        match self {
+            Scale::Linear => value,
+            Scale::Log10 => value.log10(),
+            Scale::Ln => value.ln(),
+            Scale::SymLog(threshold) => value.signum() * (value.abs() / threshold).ln_1p(),
+            Scale::Power(exponent) => value.signum() * value.abs().powf(exponent),
+        }
+    }
+
+    /// The inverse of `transform`
+    pub(crate) fn inverse(self, position: f64) -> f64 {
+        match self {
+            Scale::Linear => position,
+            Scale::Log10 => 10f64.powf(position),
+            Scale::Ln => position.exp(),
+            Scale::SymLog(threshold) => position.signum() * position.abs().exp_m1() * threshold,
+            Scale::Power(exponent) => position.signum() * position.abs().powf(exponent.recip()),
+        }
+    }
+
+    /// Whether an axis from `lower` to `upper` can be drawn with this scale
+    pub(crate) fn is_valid_range(self, lower: f64, upper: f64) -> bool {
+        match self {
+            Scale::Linear => true,
+            Scale::Log10 | Scale::Ln => lower > 0. && upper > 0.,
+            Scale::SymLog(threshold) => threshold > 0.,
+            Scale::Power(exponent) => exponent > 0.,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inverse() {
+        let scales = [
+            Scale::Linear,
+            Scale::Log10,
+            Scale::Ln,
+            Scale::SymLog(2.),
+            Scale::Power(0.5),
+        ];
+        for scale in scales.iter() {
+            for &value in [0.5, 3., 250.].iter() {
+                let round_trip = scale.inverse(scale.transform(value));
+                assert!((round_trip - value).abs() < 1e-9, "{:?}", scale);
+            }
+        }
+        assert_eq!(
+            Scale::SymLog(2.).transform(-4.),
+            -Scale::SymLog(2.).transform(4.)
+        );
+        assert_eq!(Scale::Power(2.).transform(-3.), -9.);
+    }
+
+    #[test]
+    fn test_valid_range() {
+        assert!(Scale::Linear.is_valid_range(-1., 1.));
+        assert!(Scale::Log10.is_valid_range(0.1, 10.));
+        assert!(!Scale::Log10.is_valid_range(0., 10.));
+        assert!(!Scale::Ln.is_valid_range(-1., 10.));
+        assert!(Scale::SymLog(1.).is_valid_range(-10., 10.));
+        assert!(!Scale::SymLog(0.).is_valid_range(-10., 10.));
+        assert!(!Scale::Power(-1.).is_valid_range(0., 10.));
+    }
+}
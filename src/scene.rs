@@ -0,0 +1,712 @@
+/*!
+A module for flattening a rendered SVG document into a list of simple shapes.
+
+The non-SVG backends don't know how to draw views themselves.
+Instead they take the document produced by `svg_render`,
+resolve all of its transforms and inherited attributes,
+and draw the resulting shapes in page coordinates.
+
+Only the subset of SVG which plotlib itself emits is understood.
+*/
+
+use std::collections::HashMap;
+
+use failure::format_err;
+use svg::node::element::path::{Command, Data, Position};
+use svg::node::element::tag::Type;
+use svg::node::Attributes;
+use svg::parser::Event;
+
+use crate::colour::{self, Rgb};
+use crate::errors::Result;
+
+/// An affine transformation, mapping `(x, y)` to `(a*x + c*y + e, b*x + d*y + f)`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Transform {
+    pub a: f64,
+    pub b: f64,
+    pub c: f64,
+    pub d: f64,
+    pub e: f64,
+    pub f: f64,
+}
+
+impl Transform {
+    pub fn identity() -> Self {
+        Transform::new(1., 0., 0., 1., 0., 0.)
+    }
+
+    fn new(a: f64, b: f64, c: f64, d: f64, e: f64, f: f64) -> Self {
+        Transform { a, b, c, d, e, f }
+    }
+
+    fn translate(x: f64, y: f64) -> Self {
+        Transform::new(1., 0., 0., 1., x, y)
+    }
+
+    fn rotate(degrees: f64) -> Self {
+        let (sin, cos) = degrees.to_radians().sin_cos();
+        Transform::new(cos, sin, -sin, cos, 0., 0.)
+    }
+
+    /// The transform which applies `other` first and then `self`
+    pub fn then(&self, other: &Transform) -> Transform {
+        Transform::new(
+            self.a * other.a + self.c * other.b,
+            self.b * other.a + self.d * other.b,
+            self.a * other.c + self.c * other.d,
+            self.b * other.c + self.d * other.d,
+            self.a * other.e + self.c * other.f + self.e,
+            self.b * other.e + self.d * other.f + self.f,
+        )
+    }
+
+    pub fn apply(&self, (x, y): (f64, f64)) -> (f64, f64) {
+        (
+            self.a * x + self.c * y + self.e,
+            self.b * x + self.d * y + self.f,
+        )
+    }
+
+    /// The factor by which lengths are scaled
+    pub fn scale_factor(&self) -> f64 {
+        (self.a * self.d - self.b * self.c).abs().sqrt()
+    }
+
+    /// Parse the value of an SVG `transform` attribute
+    fn parse(value: &str) -> Result<Transform> {
+        let mut transform = Transform::identity();
+        for function in value.split(')').map(str::trim).filter(|f| !f.is_empty()) {
+            let mut parts = function.splitn(2, '(');
+            let name = parts.next().unwrap_or("").trim();
+            let args = parse_numbers(parts.next().unwrap_or(""));
+            let next = match (name, &args[..]) {
+                ("translate", &[x]) => Transform::translate(x, 0.),
+                ("translate", &[x, y]) => Transform::translate(x, y),
+                ("scale", &[s]) => Transform::new(s, 0., 0., s, 0., 0.),
+                ("scale", &[sx, sy]) => Transform::new(sx, 0., 0., sy, 0., 0.),
+                ("rotate", &[angle]) => Transform::rotate(angle),
+                ("rotate", &[angle, cx, cy]) => Transform::translate(cx, cy)
+                    .then(&Transform::rotate(angle))
+                    .then(&Transform::translate(-cx, -cy)),
+                ("matrix", &[a, b, c, d, e, f]) => Transform::new(a, b, c, d, e, f),
+                _ => return Err(format_err!("Unsupported transform: {}", function)),
+            };
+            transform = transform.then(&next);
+        }
+        Ok(transform)
+    }
+}
+
+/// How the corners of stroked paths should be joined
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Join {
+    Miter,
+    Round,
+    Bevel,
+}
+
+#[derive(Debug, Clone)]
+pub struct Stroke {
+    pub colour: Rgb,
+    pub opacity: f64,
+    pub width: f64,
+    pub join: Join,
+}
+
+#[derive(Debug, Clone)]
+pub struct Fill {
+    pub colour: Rgb,
+    pub opacity: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct SubPath {
+    pub points: Vec<(f64, f64)>,
+    pub closed: bool,
+}
+
+/// Where the anchor point of a piece of text lies along its length
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Anchor {
+    Start,
+    Middle,
+    End,
+}
+
+#[derive(Debug, Clone)]
+pub struct Text {
+    pub content: String,
+    /// Maps the text's own coordinates onto the page
+    pub transform: Transform,
+    pub x: f64,
+    pub y: f64,
+    pub font_size: f64,
+    pub anchor: Anchor,
+    /// Whether `y` is the vertical centre of the text rather than its baseline
+    pub centred: bool,
+}
+
+#[derive(Debug, Clone)]
+pub enum Shape {
+    /// Any number of sub-paths, already mapped onto the page
+    Path(Vec<SubPath>),
+    /// A circle with its centre and radius already mapped onto the page
+    Circle((f64, f64), f64),
+    Text(Text),
+}
+
+/// An axis-aligned rectangle on the page, as `(left, top, right, bottom)`
+pub type Rect = (f64, f64, f64, f64);
+
+#[derive(Debug, Clone)]
+pub struct Item {
+    pub shape: Shape,
+    pub fill: Option<Fill>,
+    pub stroke: Option<Stroke>,
+    /// The area outside of which nothing of the item is drawn
+    pub clip: Option<Rect>,
+}
+
+/// A whole page of shapes, ordered from bottom to top
+#[derive(Debug)]
+pub struct Scene {
+    pub width: f64,
+    pub height: f64,
+    pub items: Vec<Item>,
+}
+
+/// The inheritable state in effect for an element
+#[derive(Debug, Clone)]
+struct State {
+    transform: Transform,
+    fill: Option<Rgb>,
+    fill_opacity: f64,
+    stroke: Option<Rgb>,
+    stroke_opacity: f64,
+    stroke_width: f64,
+    opacity: f64,
+    join: Join,
+    font_size: f64,
+    anchor: Anchor,
+    clip: Option<Rect>,
+}
+
+impl Default for State {
+    fn default() -> Self {
+        State {
+            transform: Transform::identity(),
+            fill: Some(Rgb(0, 0, 0)),
+            fill_opacity: 1.,
+            stroke: None,
+            stroke_opacity: 1.,
+            stroke_width: 1.,
+            opacity: 1.,
+            join: Join::Miter,
+            font_size: 16.,
+            anchor: Anchor::Start,
+            clip: None,
+        }
+    }
+}
+
+impl State {
+    /// Create the state for a child element with the given attributes,
+    /// looking up any clip path it refers to among those defined so far
+    fn inherit(
+        &self,
+        attributes: &Attributes,
+        clip_paths: &HashMap<String, Rect>,
+    ) -> Result<State> {
+        let mut state = self.clone();
+        if let Some(value) = attributes.get("transform") {
+            state.transform = self.transform.then(&Transform::parse(value)?);
+        }
+        if let Some(value) = attributes.get("clip-path") {
+            let id = value
+                .trim()
+                .trim_start_matches("url(#")
+                .trim_end_matches(')');
+            let &(left, top, right, bottom) = clip_paths
+                .get(id)
+                .ok_or_else(|| format_err!("Unknown clip path: {}", value))?;
+            // The clip path is drawn in the coordinates of the element which uses it
+            let corners = [
+                state.transform.apply((left, top)),
+                state.transform.apply((right, bottom)),
+            ];
+            let clip = (
+                corners[0].0.min(corners[1].0),
+                corners[0].1.min(corners[1].1),
+                corners[0].0.max(corners[1].0),
+                corners[0].1.max(corners[1].1),
+            );
+            state.clip = Some(match self.clip {
+                Some(outer) => (
+                    clip.0.max(outer.0),
+                    clip.1.max(outer.1),
+                    clip.2.min(outer.2),
+                    clip.3.min(outer.3),
+                ),
+                None => clip,
+            });
+        }
+        // An invalid colour is ignored, leaving the inherited value in place
+        if let Some(value) = attributes.get("fill") {
+            if value.trim() == "none" {
+                state.fill = None;
+            } else if let Some(colour) = colour::parse(value) {
+                state.fill = Some(colour);
+            }
+        }
+        if let Some(value) = attributes.get("stroke") {
+            if value.trim() == "none" {
+                state.stroke = None;
+            } else if let Some(colour) = colour::parse(value) {
+                state.stroke = Some(colour);
+            }
+        }
+        if let Some(value) = number(attributes, "fill-opacity") {
+            state.fill_opacity = value;
+        }
+        if let Some(value) = number(attributes, "stroke-opacity") {
+            state.stroke_opacity = value;
+        }
+        if let Some(value) = number(attributes, "opacity") {
+            state.opacity *= value;
+        }
+        if let Some(value) = number(attributes, "stroke-width") {
+            state.stroke_width = value;
+        }
+        if let Some(value) = number(attributes, "font-size") {
+            state.font_size = value;
+        }
+        match attributes.get("stroke-linejoin").map(|v| v.trim()) {
+            Some("round") => state.join = Join::Round,
+            Some("bevel") => state.join = Join::Bevel,
+            Some("miter") => state.join = Join::Miter,
+            _ => {}
+        }
+        match attributes.get("text-anchor").map(|v| v.trim()) {
+            Some("middle") => state.anchor = Anchor::Middle,
+            Some("end") => state.anchor = Anchor::End,
+            Some("start") => state.anchor = Anchor::Start,
+            _ => {}
+        }
+        Ok(state)
+    }
+
+    fn item(&self, shape: Shape) -> Item {
+        Item {
+            shape,
+            fill: self.fill.map(|colour| Fill {
+                colour,
+                opacity: self.fill_opacity * self.opacity,
+            }),
+            stroke: self.stroke.map(|colour| Stroke {
+                colour,
+                opacity: self.stroke_opacity * self.opacity,
+                width: self.stroke_width * self.transform.scale_factor(),
+                join: self.join,
+            }),
+            clip: self.clip,
+        }
+    }
+}
+
+fn parse_numbers(s: &str) -> Vec<f64> {
+    s.split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|n| !n.is_empty())
+        .filter_map(|n| n.parse().ok())
+        .collect()
+}
+
+fn number(attributes: &Attributes, name: &str) -> Option<f64> {
+    attributes.get(name).and_then(|v| v.trim().parse().ok())
+}
+
+fn coordinate(attributes: &Attributes, name: &str) -> f64 {
+    number(attributes, name).unwrap_or(0.)
+}
+
+fn unescape(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Convert the commands of an SVG path into sub-paths in the element's own coordinates
+fn path_data(data: &Data) -> Vec<SubPath> {
+    let mut subpaths: Vec<SubPath> = vec![];
+    let mut current = (0., 0.);
+    let mut start = (0., 0.);
+
+    fn resolve(position: &Position, current: (f64, f64), x: f64, y: f64) -> (f64, f64) {
+        match position {
+            Position::Absolute => (x, y),
+            Position::Relative => (current.0 + x, current.1 + y),
+        }
+    }
+
+    for command in data.iter() {
+        match command {
+            Command::Move(position, params) => {
+                for (i, pair) in params.chunks(2).filter(|p| p.len() == 2).enumerate() {
+                    current = resolve(position, current, f64::from(pair[0]), f64::from(pair[1]));
+                    if i == 0 {
+                        start = current;
+                        subpaths.push(SubPath {
+                            points: vec![current],
+                            closed: false,
+                        });
+                    } else if let Some(subpath) = subpaths.last_mut() {
+                        subpath.points.push(current);
+                    }
+                }
+            }
+            Command::Line(position, params) => {
+                for pair in params.chunks(2).filter(|p| p.len() == 2) {
+                    current = resolve(position, current, f64::from(pair[0]), f64::from(pair[1]));
+                    if let Some(subpath) = subpaths.last_mut() {
+                        subpath.points.push(current);
+                    }
+                }
+            }
+            Command::HorizontalLine(position, params) => {
+                for &x in params.iter() {
+                    current = match position {
+                        Position::Absolute => (f64::from(x), current.1),
+                        Position::Relative => (current.0 + f64::from(x), current.1),
+                    };
+                    if let Some(subpath) = subpaths.last_mut() {
+                        subpath.points.push(current);
+                    }
+                }
+            }
+            Command::VerticalLine(position, params) => {
+                for &y in params.iter() {
+                    current = match position {
+                        Position::Absolute => (current.0, f64::from(y)),
+                        Position::Relative => (current.0, current.1 + f64::from(y)),
+                    };
+                    if let Some(subpath) = subpaths.last_mut() {
+                        subpath.points.push(current);
+                    }
+                }
+            }
+            Command::Close => {
+                if let Some(subpath) = subpaths.last_mut() {
+                    subpath.closed = true;
+                }
+                current = start;
+            }
+            // plotlib never emits curves
+            _ => {}
+        }
+    }
+    subpaths
+}
+
+fn shape(name: &str, attributes: &Attributes, state: &State) -> Result<Option<Shape>> {
+    let transform = &state.transform;
+    let map = |subpaths: Vec<SubPath>| {
+        subpaths
+            .into_iter()
+            .map(|s| SubPath {
+                points: s.points.into_iter().map(|p| transform.apply(p)).collect(),
+                closed: s.closed,
+            })
+            .collect()
+    };
+    let shape = match name {
+        "line" => Shape::Path(map(vec![SubPath {
+            points: vec![
+                (coordinate(attributes, "x1"), coordinate(attributes, "y1")),
+                (coordinate(attributes, "x2"), coordinate(attributes, "y2")),
+            ],
+            closed: false,
+        }])),
+        "rect" => {
+            let (x, y) = (coordinate(attributes, "x"), coordinate(attributes, "y"));
+            let (w, h) = (
+                coordinate(attributes, "width"),
+                coordinate(attributes, "height"),
+            );
+            Shape::Path(map(vec![SubPath {
+                points: vec![(x, y), (x + w, y), (x + w, y + h), (x, y + h)],
+                closed: true,
+            }]))
+        }
+        "circle" => Shape::Circle(
+            transform.apply((coordinate(attributes, "cx"), coordinate(attributes, "cy"))),
+            coordinate(attributes, "r") * transform.scale_factor(),
+        ),
+        "polygon" | "polyline" => {
+            let numbers = parse_numbers(attributes.get("points").map_or("", |p| p));
+            Shape::Path(map(vec![SubPath {
+                points: numbers.chunks(2).map(|p| (p[0], p[1])).collect(),
+                closed: name == "polygon",
+            }]))
+        }
+        "path" => {
+            let data = match attributes.get("d") {
+                Some(d) => Data::parse(d).map_err(|e| format_err!("Invalid path: {}", e))?,
+                None => return Ok(None),
+            };
+            Shape::Path(map(path_data(&data)))
+        }
+        _ => return Ok(None),
+    };
+    Ok(Some(shape))
+}
+
+impl Scene {
+    /// Move every item across and down the page
+    pub fn translate(&mut self, dx: f64, dy: f64) {
+        let shift = Transform::translate(dx, dy);
+        for item in &mut self.items {
+            match &mut item.shape {
+                Shape::Path(sub_paths) => {
+                    for sub_path in sub_paths {
+                        for point in &mut sub_path.points {
+                            *point = shift.apply(*point);
+                        }
+                    }
+                }
+                Shape::Circle(centre, _) => *centre = shift.apply(*centre),
+                Shape::Text(text) => text.transform = shift.then(&text.transform),
+            }
+            if let Some((left, top, right, bottom)) = &mut item.clip {
+                *left += dx;
+                *right += dx;
+                *top += dy;
+                *bottom += dy;
+            }
+        }
+    }
+
+    /// Flatten an SVG document into a scene
+    pub fn from_document(document: &svg::Document) -> Result<Scene> {
+        let content = document.to_string();
+        let mut scene = Scene {
+            width: 0.,
+            height: 0.,
+            items: vec![],
+        };
+        let mut stack = vec![State::default()];
+        // The text element currently being read, if any
+        let mut text: Option<(Text, State)> = None;
+        // The rectangles of the clip paths defined so far,
+        // and the id of the one currently being read, if any
+        let mut clip_paths: HashMap<String, Rect> = HashMap::new();
+        let mut clip_path: Option<String> = None;
+
+        for event in svg::read(content.as_bytes())? {
+            match event {
+                Event::Error(e) => return Err(format_err!("Could not read SVG: {}", e)),
+                Event::Tag(name, Type::End, _) => {
+                    if name == "clipPath" {
+                        clip_path = None;
+                    }
+                    if name == "text" {
+                        if let Some((mut t, state)) = text.take() {
+                            t.content = unescape(t.content.trim());
+                            scene.items.push(state.item(Shape::Text(t)));
+                        }
+                    }
+                    stack.pop();
+                }
+                Event::Tag(name, tag_type, attributes) => {
+                    let state = stack.last().cloned().unwrap_or_default();
+                    let state = state.inherit(&attributes, &clip_paths)?;
+                    match name {
+                        "svg" => {
+                            if let Some(view_box) = attributes.get("viewBox") {
+                                if let [_, _, width, height] = parse_numbers(view_box)[..] {
+                                    scene.width = width;
+                                    scene.height = height;
+                                }
+                            }
+                        }
+                        "clipPath" => {
+                            clip_path = attributes.get("id").map(|id| id.to_string());
+                        }
+                        // Only rectangles, in the coordinates of the element using them, are understood
+                        "rect" if clip_path.is_some() => {
+                            let (x, y) =
+                                (coordinate(&attributes, "x"), coordinate(&attributes, "y"));
+                            let (w, h) = (
+                                coordinate(&attributes, "width"),
+                                coordinate(&attributes, "height"),
+                            );
+                            if let Some(id) = &clip_path {
+                                clip_paths.insert(id.clone(), (x, y, x + w, y + h));
+                            }
+                        }
+                        _ if clip_path.is_some() => {}
+                        "text" => {
+                            text = Some((
+                                Text {
+                                    content: String::new(),
+                                    transform: state.transform,
+                                    x: coordinate(&attributes, "x"),
+                                    y: coordinate(&attributes, "y"),
+                                    font_size: state.font_size,
+                                    anchor: state.anchor,
+                                    centred: attributes
+                                        .get("dominant-baseline")
+                                        .is_some_and(|b| b.trim() == "middle"),
+                                },
+                                state.clone(),
+                            ));
+                        }
+                        _ => {
+                            if let Some(shape) = shape(name, &attributes, &state)? {
+                                scene.items.push(state.item(shape));
+                            }
+                        }
+                    }
+                    if let Type::Start = tag_type {
+                        stack.push(state);
+                    }
+                }
+                Event::Text(content) => {
+                    if let Some((ref mut t, _)) = text {
+                        t.content.push_str(content);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        if scene.width <= 0. || scene.height <= 0. {
+            return Err(format_err!("SVG document has no viewBox"));
+        }
+        Ok(scene)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transform_parse() {
+        let t = Transform::parse("translate(72, 364)").unwrap();
+        assert_eq!(t.apply((1., 2.)), (73., 366.));
+
+        let t = Transform::parse("rotate(-90 10 20)").unwrap();
+        let (x, y) = t.apply((10., 30.));
+        assert!((x - 20.).abs() < 1e-9 && (y - 20.).abs() < 1e-9);
+
+        let t = Transform::parse("translate(5 0) scale(2)").unwrap();
+        assert_eq!(t.apply((1., 1.)), (7., 2.));
+        assert_eq!(t.scale_factor(), 2.);
+
+        assert!(Transform::parse("skewX(3)").is_err());
+    }
+
+    #[test]
+    fn test_scene_from_document() {
+        use svg::node::element::{Group, Line, Rectangle, Text};
+        use svg::Node;
+
+        let mut group = Group::new().set("transform", "translate(10, 20)");
+        group.append(
+            Rectangle::new()
+                .set("x", 0)
+                .set("y", 0)
+                .set("width", 5)
+                .set("height", 5)
+                .set("fill", "")
+                .set("stroke", "black"),
+        );
+        group.append(Line::new().set("x1", 0).set("x2", 3).set("stroke", "red"));
+        group.append(
+            Text::new()
+                .set("font-size", 12)
+                .set("text-anchor", "middle")
+                .add(svg::node::Text::new("label")),
+        );
+        let document = svg::Document::new()
+            .set("viewBox", (0, 0, 100, 50))
+            .add(group);
+
+        let scene = Scene::from_document(&document).unwrap();
+        assert_eq!((scene.width, scene.height), (100., 50.));
+        assert_eq!(scene.items.len(), 3);
+
+        match &scene.items[0].shape {
+            Shape::Path(subpaths) => {
+                assert!(subpaths[0].closed);
+                assert_eq!(subpaths[0].points[2], (15., 25.));
+            }
+            _ => panic!("expected a path"),
+        }
+        // An invalid fill falls back to the default of black
+        assert_eq!(scene.items[0].fill.as_ref().unwrap().colour, Rgb(0, 0, 0));
+
+        // Lines are filled by default but the fill of a line has no area
+        assert_eq!(
+            scene.items[1].stroke.as_ref().unwrap().colour,
+            Rgb(255, 0, 0)
+        );
+
+        match &scene.items[2].shape {
+            Shape::Text(t) => {
+                assert_eq!(t.content, "label");
+                assert_eq!(t.anchor, Anchor::Middle);
+                assert_eq!(t.font_size, 12.);
+                assert_eq!(t.transform.apply((0., 0.)), (10., 20.));
+            }
+            _ => panic!("expected text"),
+        }
+
+        let mut scene = scene;
+        scene.translate(5., -10.);
+        match (&scene.items[0].shape, &scene.items[2].shape) {
+            (Shape::Path(subpaths), Shape::Text(t)) => {
+                assert_eq!(subpaths[0].points[2], (20., 15.));
+                assert_eq!(t.transform.apply((0., 0.)), (15., 10.));
+            }
+            _ => panic!("expected a path and text"),
+        }
+    }
+
+    #[test]
+    fn test_clip_path() {
+        use svg::node::element::{ClipPath, Group, Line, Rectangle};
+
+        let clip_path = ClipPath::new().set("id", "face").add(
+            Rectangle::new()
+                .set("x", 0)
+                .set("y", -10)
+                .set("width", 20)
+                .set("height", 10),
+        );
+        let face = Group::new()
+            .set("clip-path", "url(#face)")
+            .add(Line::new().set("x2", 50).set("stroke", "black"));
+        let group = Group::new()
+            .set("transform", "translate(5, 30)")
+            .add(clip_path)
+            .add(face)
+            .add(Line::new().set("x2", 50).set("stroke", "black"));
+        let document = svg::Document::new()
+            .set("viewBox", (0, 0, 100, 50))
+            .add(group);
+
+        let scene = Scene::from_document(&document).unwrap();
+        // The rectangle of the clip path is not drawn itself
+        assert_eq!(scene.items.len(), 2);
+        assert_eq!(scene.items[0].clip, Some((5., 20., 25., 30.)));
+        assert_eq!(scene.items[1].clip, None);
+
+        let unknown = svg::Document::new()
+            .set("viewBox", (0, 0, 100, 50))
+            .add(Group::new().set("clip-path", "url(#missing)"));
+        assert!(Scene::from_document(&unknown).is_err());
+    }
+}
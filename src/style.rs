@@ -60,7 +60,7 @@ impl LineStyle {
         self
     }
     pub fn get_width(&self) -> f32 {
-        self.width.unwrap_or_else(|| 2.0)
+        self.width.unwrap_or(2.0)
     }
 
     pub fn linejoin<T>(mut self, value: T) -> Self
@@ -71,7 +71,7 @@ impl LineStyle {
         self
     }
     pub fn get_linejoin(&self) -> LineJoin {
-        self.linejoin.unwrap_or_else(|| LineJoin::Round)
+        self.linejoin.unwrap_or(LineJoin::Round)
     }
 }
 
@@ -172,6 +172,123 @@ impl BoxStyle {
     }
 }
 
+/// Fills which are given in turn to the layers of a stacked area, unless they are styled
+pub const PALETTE: [&str; 8] = [
+    "#4e79a7", "#f28e2b", "#e15759", "#76b7b2", "#59a14f", "#edc948", "#b07aa1", "#9c755f",
+];
+
+/// The style of the filled region of an area
+#[derive(Debug, Default, Clone)]
+pub struct AreaStyle {
+    fill: Option<String>,
+    opacity: Option<f32>,
+}
+impl AreaStyle {
+    pub fn new() -> Self {
+        AreaStyle {
+            fill: None,
+            opacity: None,
+        }
+    }
+
+    pub fn overlay(&mut self, other: &Self) {
+        if let Some(ref v) = other.fill {
+            self.fill = Some(v.clone())
+        }
+
+        if let Some(v) = other.opacity {
+            self.opacity = Some(v)
+        }
+    }
+
+    pub fn fill<T>(mut self, value: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.fill = Some(value.into());
+        self
+    }
+    pub fn get_fill(&self) -> String {
+        self.fill.clone().unwrap_or_else(|| "grey".into())
+    }
+
+    /// Set how opaque the fill is, from 0 for invisible to 1 for solid
+    pub fn opacity<T>(mut self, value: T) -> Self
+    where
+        T: Into<f32>,
+    {
+        self.opacity = Some(value.into());
+        self
+    }
+    pub fn get_opacity(&self) -> f32 {
+        self.opacity.unwrap_or(1.0)
+    }
+}
+
+/// The style of the error bars of a plot
+#[derive(Debug, Default, Clone)]
+pub struct ErrorBarStyle {
+    colour: Option<String>,
+    width: Option<f32>,
+    cap_width: Option<f32>,
+}
+impl ErrorBarStyle {
+    pub fn new() -> Self {
+        ErrorBarStyle {
+            colour: None,
+            width: None,
+            cap_width: None,
+        }
+    }
+
+    pub fn overlay(&mut self, other: &Self) {
+        if let Some(ref v) = other.colour {
+            self.colour = Some(v.clone())
+        }
+
+        if let Some(v) = other.width {
+            self.width = Some(v)
+        }
+
+        if let Some(v) = other.cap_width {
+            self.cap_width = Some(v)
+        }
+    }
+    pub fn colour<T>(mut self, value: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.colour = Some(value.into());
+        self
+    }
+    pub fn get_colour(&self) -> String {
+        self.colour.clone().unwrap_or_else(|| "black".into())
+    }
+
+    pub fn width<T>(mut self, value: T) -> Self
+    where
+        T: Into<f32>,
+    {
+        self.width = Some(value.into());
+        self
+    }
+    pub fn get_width(&self) -> f32 {
+        self.width.unwrap_or(1.0)
+    }
+
+    /// Set the length of the caps across the ends of each bar, or zero to leave them off
+    pub fn cap_width<T>(mut self, value: T) -> Self
+    where
+        T: Into<f32>,
+    {
+        self.cap_width = Some(value.into());
+        self
+    }
+    pub fn get_cap_width(&self) -> f32 {
+        self.cap_width.unwrap_or(6.0)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -192,4 +309,13 @@ mod tests {
             panic!()
         }
     }
+
+    #[test]
+    fn test_errorbarstyle_overlay() {
+        let mut p = ErrorBarStyle::new().colour("red").cap_width(4.);
+        p.overlay(&ErrorBarStyle::new().width(2.).cap_width(0.));
+        assert_eq!(p.get_colour(), "red".to_string());
+        assert_eq!(p.get_width(), 2.);
+        assert_eq!(p.get_cap_width(), 0.);
+    }
 }
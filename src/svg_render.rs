@@ -1,18 +1,17 @@
-use std;
+use std::cell::Cell;
 
 use svg::node;
 use svg::Node;
 
 use crate::axis;
-use crate::grid::GridType;
+use crate::grid::{Grid, GridLines};
 use crate::repr;
 use crate::style;
 use crate::utils;
 use crate::utils::PairWise;
 
 fn value_to_face_offset(value: f64, axis: &axis::ContinuousAxis, face_size: f64) -> f64 {
-    let range = axis.max() - axis.min();
-    (face_size * (value - axis.min())) / range
+    face_size * axis.fraction(value)
 }
 
 fn vertical_line<S>(xpos: f64, ymin: f64, ymax: f64, color: S) -> node::element::Line
@@ -41,30 +40,49 @@ where
         .set("stroke-width", 1)
 }
 
-pub fn draw_x_axis(a: &axis::ContinuousAxis, face_width: f64) -> node::element::Group {
-    let axis_line = horizontal_line(0.0, 0.0, face_width, "black");
-
+/// Draw an x-axis in segments, each given with its offset along the face and its width
+///
+/// An axis broken into more than one segment is marked at each side of the gaps between them.
+/// The axis is labelled with the label of the first segment.
+pub fn draw_x_axis(
+    segments: &[(&axis::ContinuousAxis, f64, f64)],
+    face_width: f64,
+) -> node::element::Group {
     let mut ticks = node::element::Group::new();
     let mut labels = node::element::Group::new();
 
-    for &tick in a.ticks().iter() {
-        let tick_pos = value_to_face_offset(tick, a, face_width);
-        let tick_mark = node::element::Line::new()
-            .set("x1", tick_pos)
-            .set("y1", 0)
-            .set("x2", tick_pos)
-            .set("y2", 10)
-            .set("stroke", "black")
-            .set("stroke-width", 1);
-        ticks.append(tick_mark);
+    for &(a, offset, width) in segments {
+        for &tick in a.ticks().iter() {
+            let tick_pos = offset + value_to_face_offset(tick, a, width);
+            let tick_mark = node::element::Line::new()
+                .set("x1", tick_pos)
+                .set("y1", 0)
+                .set("x2", tick_pos)
+                .set("y2", 10)
+                .set("stroke", "black")
+                .set("stroke-width", 1);
+            ticks.append(tick_mark);
+
+            let tick_label = node::element::Text::new()
+                .set("x", tick_pos)
+                .set("y", 20)
+                .set("text-anchor", "middle")
+                .set("font-size", 12)
+                .add(node::Text::new(a.tick_label(tick)));
+            labels.append(tick_label);
+        }
 
-        let tick_label = node::element::Text::new()
-            .set("x", tick_pos)
-            .set("y", 20)
-            .set("text-anchor", "middle")
-            .set("font-size", 12)
-            .add(node::Text::new(tick.to_string()));
-        labels.append(tick_label);
+        for &tick in a.minor_ticks().iter() {
+            let tick_pos = offset + value_to_face_offset(tick, a, width);
+            let tick_mark = node::element::Line::new()
+                .set("x1", tick_pos)
+                .set("y1", 0)
+                .set("x2", tick_pos)
+                .set("y2", 5)
+                .set("stroke", "black")
+                .set("stroke-width", 1);
+            ticks.append(tick_mark);
+        }
     }
 
     let label = node::element::Text::new()
@@ -72,54 +90,101 @@ pub fn draw_x_axis(a: &axis::ContinuousAxis, face_width: f64) -> node::element::
         .set("y", 30)
         .set("text-anchor", "middle")
         .set("font-size", 12)
-        .add(node::Text::new(a.get_label()));
+        .add(node::Text::new(
+            segments.first().map_or("", |(a, _, _)| a.get_label()),
+        ));
 
-    node::element::Group::new()
-        .add(ticks)
-        .add(axis_line)
-        .add(labels)
-        .add(label)
+    let mut group = node::element::Group::new().add(ticks);
+    for (start, end) in segment_extents(segments) {
+        group.append(horizontal_line(0.0, start, end, "black"));
+    }
+    for position in segment_breaks(segments) {
+        group.append(break_mark(position, 0., 3., 5.));
+    }
+    group.add(labels).add(label)
 }
 
-pub fn draw_y_axis(a: &axis::ContinuousAxis, face_height: f64) -> node::element::Group {
-    let axis_line = vertical_line(0.0, 0.0, -face_height, "black");
+/// Draw a y-axis in segments, each given with its offset up the face and its height
+///
+/// An axis broken into more than one segment is marked at each side of the gaps between them.
+/// The axis is labelled with the label of the first segment.
+pub fn draw_y_axis(
+    segments: &[(&axis::ContinuousAxis, f64, f64)],
+    face_height: f64,
+) -> node::element::Group {
+    draw_vertical_axis(segments, face_height, -1.)
+}
 
+/// Draw a y-axis up the right-hand side of the face, with its ticks and labels facing right
+pub(crate) fn draw_secondary_y_axis(
+    a: &axis::ContinuousAxis,
+    face_width: f64,
+    face_height: f64,
+) -> node::element::Group {
+    draw_vertical_axis(&[(a, 0., face_height)], face_height, 1.)
+        .set("transform", format!("translate({}, 0)", face_width))
+}
+
+/// Draw a vertical axis with its ticks and labels on the left (`side` of -1) or right (`side` of 1)
+fn draw_vertical_axis(
+    segments: &[(&axis::ContinuousAxis, f64, f64)],
+    face_height: f64,
+    side: f64,
+) -> node::element::Group {
     let mut ticks = node::element::Group::new();
     let mut labels = node::element::Group::new();
 
     let y_tick_font_size = 12;
 
-    for &tick in a.ticks().iter() {
-        let tick_pos = value_to_face_offset(tick, a, face_height);
-        let tick_mark = node::element::Line::new()
-            .set("x1", 0)
-            .set("y1", -tick_pos)
-            .set("x2", -10)
-            .set("y2", -tick_pos)
-            .set("stroke", "black")
-            .set("stroke-width", 1);
-        ticks.append(tick_mark);
+    for &(a, offset, height) in segments {
+        for &tick in a.ticks().iter() {
+            let tick_pos = offset + value_to_face_offset(tick, a, height);
+            let tick_mark = node::element::Line::new()
+                .set("x1", 0)
+                .set("y1", -tick_pos)
+                .set("x2", side * 10.)
+                .set("y2", -tick_pos)
+                .set("stroke", "black")
+                .set("stroke-width", 1);
+            ticks.append(tick_mark);
+
+            let tick_label = node::element::Text::new()
+                .set("x", side * 15.)
+                .set("y", -tick_pos)
+                .set("text-anchor", if side < 0. { "end" } else { "start" })
+                .set("dominant-baseline", "middle")
+                .set("font-size", y_tick_font_size)
+                .add(node::Text::new(a.tick_label(tick)));
+            labels.append(tick_label);
+        }
 
-        let tick_label = node::element::Text::new()
-            .set("x", -15)
-            .set("y", -tick_pos)
-            .set("text-anchor", "end")
-            .set("dominant-baseline", "middle")
-            .set("font-size", y_tick_font_size)
-            .add(node::Text::new(tick.to_string()));
-        labels.append(tick_label);
+        for &tick in a.minor_ticks().iter() {
+            let tick_pos = offset + value_to_face_offset(tick, a, height);
+            let tick_mark = node::element::Line::new()
+                .set("x1", 0)
+                .set("y1", -tick_pos)
+                .set("x2", side * 5.)
+                .set("y2", -tick_pos)
+                .set("stroke", "black")
+                .set("stroke-width", 1);
+            ticks.append(tick_mark);
+        }
     }
 
-    let max_tick_length = a
-        .ticks()
+    let max_tick_length = segments
         .iter()
-        .map(|&t| t.to_string().len())
+        .flat_map(|(a, _, _)| {
+            a.ticks()
+                .iter()
+                .map(move |&t| a.tick_label(t).chars().count())
+        })
         .max()
-        .expect("Could not calculate max tick length");
+        .unwrap_or(0);
 
-    let x_offset = -(y_tick_font_size * max_tick_length as i32);
+    let x_offset = side * f64::from(y_tick_font_size * max_tick_length as i32);
     let y_label_offset = -(face_height / 2.);
     let y_label_font_size = 12;
+    // The label reads upwards on the left and downwards on the right, away from the face
     let label = node::element::Text::new()
         .set("x", x_offset)
         .set("y", y_label_offset - f64::from(y_label_font_size))
@@ -127,15 +192,52 @@ pub fn draw_y_axis(a: &axis::ContinuousAxis, face_height: f64) -> node::element:
         .set("font-size", y_label_font_size)
         .set(
             "transform",
-            format!("rotate(-90 {} {})", x_offset, y_label_offset),
+            format!("rotate({} {} {})", side * 90., x_offset, y_label_offset),
         )
-        .add(node::Text::new(a.get_label()));
+        .add(node::Text::new(
+            segments.first().map_or("", |(a, _, _)| a.get_label()),
+        ));
 
-    node::element::Group::new()
-        .add(ticks)
-        .add(axis_line)
-        .add(labels)
-        .add(label)
+    let mut group = node::element::Group::new().add(ticks);
+    for (start, end) in segment_extents(segments) {
+        group.append(vertical_line(0.0, -start, -end, "black"));
+    }
+    for position in segment_breaks(segments) {
+        group.append(break_mark(0., -position, 5., 3.));
+    }
+    group.add(labels).add(label)
+}
+
+/// The start and end of each segment of an axis, in order along the face
+fn segment_extents(segments: &[(&axis::ContinuousAxis, f64, f64)]) -> Vec<(f64, f64)> {
+    let mut extents: Vec<_> = segments
+        .iter()
+        .map(|&(_, offset, size)| (offset, offset + size))
+        .collect();
+    extents.sort_by(|a, b| {
+        a.0.partial_cmp(&b.0)
+            .expect("segment offsets are never NaN")
+    });
+    extents
+}
+
+/// The positions along the face at which an axis is broken, at either side of each gap
+fn segment_breaks(segments: &[(&axis::ContinuousAxis, f64, f64)]) -> Vec<f64> {
+    segment_extents(segments)
+        .pairwise()
+        .flat_map(|(&(_, end), &(start, _))| vec![end, start])
+        .collect()
+}
+
+/// A short slanted stroke across an axis at `(x, y)`, marking where it is broken
+fn break_mark(x: f64, y: f64, half_width: f64, half_height: f64) -> node::element::Line {
+    node::element::Line::new()
+        .set("x1", x - half_width)
+        .set("y1", y + half_height)
+        .set("x2", x + half_width)
+        .set("y2", y - half_height)
+        .set("stroke", "black")
+        .set("stroke-width", 1)
 }
 
 pub fn draw_categorical_x_axis(a: &axis::CategoricalAxis, face_width: f64) -> node::element::Group {
@@ -251,16 +353,17 @@ pub fn draw_face_bars(
 ) -> node::element::Group {
     let mut group = node::element::Group::new();
 
+    // Bars stand on the minimum of the y-axis, which is at the top of the face if it is reversed
+    let base = value_to_face_offset(y_axis.min(), y_axis, face_height);
     for ((&l, &u), &count) in h.bin_bounds.pairwise().zip(h.get_values()) {
         let l_pos = value_to_face_offset(l, x_axis, face_width);
         let u_pos = value_to_face_offset(u, x_axis, face_width);
-        let width = u_pos - l_pos;
         let count_scaled = value_to_face_offset(count, y_axis, face_height);
         let rect = node::element::Rectangle::new()
-            .set("x", l_pos)
-            .set("y", -count_scaled)
-            .set("width", width)
-            .set("height", count_scaled)
+            .set("x", l_pos.min(u_pos))
+            .set("y", -count_scaled.max(base))
+            .set("width", (u_pos - l_pos).abs())
+            .set("height", (count_scaled - base).abs())
             .set("fill", style.get_fill())
             .set("stroke", "black");
         group.append(rect);
@@ -316,6 +419,129 @@ pub fn draw_face_line(
     group
 }
 
+/// Draw the region between an upper and a lower edge as one closed path,
+/// following the upper edge along and the lower edge back
+pub fn draw_face_area(
+    upper: &[(f64, f64)],
+    lower: &[(f64, f64)],
+    x_axis: &axis::ContinuousAxis,
+    y_axis: &axis::ContinuousAxis,
+    face_width: f64,
+    face_height: f64,
+    style: &style::AreaStyle,
+) -> node::element::Group {
+    let outline: Vec<_> = upper
+        .iter()
+        .chain(lower.iter().rev())
+        .map(|&(x, y)| {
+            (
+                value_to_face_offset(x, x_axis, face_width),
+                -value_to_face_offset(y, y_axis, face_height),
+            )
+        })
+        .filter(|(x, y)| x.is_finite() && y.is_finite())
+        .collect();
+
+    let mut group = node::element::Group::new();
+    if let Some((&first, rest)) = outline.split_first() {
+        let d = rest
+            .iter()
+            .fold(node::element::path::Data::new().move_to(first), |d, &p| {
+                d.line_to(p)
+            })
+            .close();
+        group.append(
+            node::element::Path::new()
+                .set("fill", style.get_fill())
+                .set("fill-opacity", style.get_opacity())
+                .set("stroke", "none")
+                .set("d", d),
+        );
+    }
+    group
+}
+
+/// Draw a legend entry for an area, with a swatch of its fill
+/// outlined in the colour of its edges if they are drawn
+pub(crate) fn draw_area_legend(
+    legend: &str,
+    style: &style::AreaStyle,
+    edge_style: Option<&style::LineStyle>,
+) -> node::element::Group {
+    const FONT_SIZE: f32 = 12.0;
+
+    let legend_text = node::element::Text::new()
+        .set("x", 0)
+        .set("y", 0)
+        .set("text-anchor", "start")
+        .set("font-size", FONT_SIZE)
+        .add(node::Text::new(legend));
+
+    let mut swatch = node::element::Rectangle::new()
+        .set("x", -11)
+        .set("y", -FONT_SIZE / 2. - 3.)
+        .set("width", 8)
+        .set("height", 8)
+        .set("fill", style.get_fill())
+        .set("fill-opacity", style.get_opacity());
+    if let Some(edge_style) = edge_style {
+        swatch = swatch
+            .set("stroke-width", 1)
+            .set("stroke", edge_style.get_colour());
+    }
+
+    node::element::Group::new().add(legend_text).add(swatch)
+}
+
+/// Draw error bars between the given ends as one path,
+/// with a cap across each end unless the cap width is zero
+pub fn draw_face_error_bars(
+    bars: &[repr::ErrorBar],
+    x_axis: &axis::ContinuousAxis,
+    y_axis: &axis::ContinuousAxis,
+    face_width: f64,
+    face_height: f64,
+    style: &style::ErrorBarStyle,
+) -> node::element::Group {
+    let half_cap = f64::from(style.get_cap_width()) / 2.;
+    let mut d = node::element::path::Data::new();
+    for &((x1, y1), (x2, y2)) in bars {
+        let start = (
+            value_to_face_offset(x1, x_axis, face_width),
+            -value_to_face_offset(y1, y_axis, face_height),
+        );
+        let end = (
+            value_to_face_offset(x2, x_axis, face_width),
+            -value_to_face_offset(y2, y_axis, face_height),
+        );
+        if !(start.0.is_finite() && start.1.is_finite() && end.0.is_finite() && end.1.is_finite()) {
+            continue;
+        }
+        d = d.move_to(start).line_to(end);
+        if half_cap > 0. {
+            // Caps run across the bar, so along y for a horizontal one
+            let (across_x, across_y) = if y1 == y2 {
+                (0., half_cap)
+            } else {
+                (half_cap, 0.)
+            };
+            for &(x, y) in &[start, end] {
+                d = d
+                    .move_to((x - across_x, y - across_y))
+                    .line_to((x + across_x, y + across_y));
+            }
+        }
+    }
+
+    node::element::Group::new().add(
+        node::element::Path::new()
+            .set("fill", "none")
+            .set("stroke", style.get_colour())
+            .set("stroke-width", style.get_width())
+            .set("d", d),
+    )
+}
+
 pub fn draw_face_boxplot<L>(
     d: &[f64],
     label: &L,
@@ -426,45 +652,127 @@ where
     group
 }
 
-pub(crate) fn draw_grid(grid: GridType, face_width: f64, face_height: f64) -> node::element::Group {
-    match grid {
-        GridType::HorizontalOnly(grid) => {
-            let (ymin, ymax) = (0f64, face_height);
-            let y_step = (ymax - ymin) / f64::from(grid.ny);
-            let mut lines = node::element::Group::new();
-
-            for iy in 0..=grid.ny {
-                let y = f64::from(iy) * y_step + ymin;
-                let line = horizontal_line(-y, 0.0, face_width, grid.color.as_str());
-                lines = lines.add(line);
-            }
+pub(crate) fn draw_grid(
+    grid: &Grid,
+    grid_lines: &GridLines,
+    face_width: f64,
+    face_height: f64,
+) -> node::element::Group {
+    let mut lines = node::element::Group::new();
 
-            lines
+    // Draw the minor lines first so that the major lines sit on top of them
+    if let Some((color, width)) = &grid.minor {
+        for &y in &grid_lines.minor_y {
+            let line = horizontal_line(-y * face_height, 0.0, face_width, color);
+            lines = lines.add(line.set("stroke-width", *width));
+        }
+        for &x in &grid_lines.minor_x {
+            let line = vertical_line(x * face_width, 0.0, -face_height, color);
+            lines = lines.add(line.set("stroke-width", *width));
         }
-        GridType::Both(grid) => {
-            let (xmin, xmax) = (0f64, face_width);
-            let (ymin, ymax) = (0f64, face_height);
+    }
+
+    for &y in &grid_lines.y {
+        let line = horizontal_line(-y * face_height, 0.0, face_width, grid.color.as_str());
+        lines = lines.add(line.set("stroke-width", grid.width));
+    }
+    for &x in &grid_lines.x {
+        let line = vertical_line(x * face_width, 0.0, -face_height, grid.color.as_str());
+        lines = lines.add(line.set("stroke-width", grid.width));
+    }
 
-            let x_step = (xmax - xmin) / f64::from(grid.nx);
-            let y_step = (ymax - ymin) / f64::from(grid.ny);
+    lines
+}
 
-            let mut lines = node::element::Group::new();
+thread_local! {
+    /// The number of the next clip path, so that each view on a page refers to its own
+    static NEXT_CLIP_ID: Cell<usize> = const { Cell::new(0) };
+}
 
-            for iy in 0..=grid.ny {
-                let y = f64::from(iy) * y_step + ymin;
-                let line = horizontal_line(-y, 0.0, face_width, grid.color.as_str());
-                lines = lines.add(line);
-            }
+/// Start numbering clip paths from the beginning, for a new document
+pub(crate) fn reset_clip_ids() {
+    NEXT_CLIP_ID.with(|id| id.set(0));
+}
 
-            for ix in 0..=grid.nx {
-                let x = f64::from(ix) * x_step + xmin;
-                let line = vertical_line(x, 0.0, -face_height, grid.color.as_str());
-                lines = lines.add(line);
-            }
+/// Clip the contents of a group to the face, so that nothing outside the axes' ranges is drawn
+pub(crate) fn clip_to_face(
+    face: node::element::Group,
+    face_width: f64,
+    face_height: f64,
+) -> node::element::Group {
+    let id = NEXT_CLIP_ID.with(|id| {
+        let current = id.get();
+        id.set(current + 1);
+        format!("plotlib-face-{}", current)
+    });
+    let clip_path = node::element::ClipPath::new().set("id", id.as_str()).add(
+        node::element::Rectangle::new()
+            .set("x", 0)
+            .set("y", -face_height)
+            .set("width", face_width)
+            .set("height", face_height),
+    );
+    node::element::Group::new()
+        .add(clip_path)
+        .add(face.set("clip-path", format!("url(#{})", id)))
+}
 
-            lines
+/// Draw a small arrow on the edge of the face for each point which lies outside it,
+/// pointing in the direction of the point
+pub(crate) fn draw_edge_markers(
+    points: &[(f64, f64)],
+    x_axis: &axis::ContinuousAxis,
+    y_axis: &axis::ContinuousAxis,
+    face_width: f64,
+    face_height: f64,
+) -> node::element::Group {
+    const SIZE: f64 = 6.;
+    let mut group = node::element::Group::new();
+    for &(x, y) in points {
+        let (x_fraction, y_fraction) = (x_axis.fraction(x), y_axis.fraction(y));
+        if x_fraction.is_nan() || y_fraction.is_nan() {
+            continue;
         }
+        let direction = |fraction: f64| -> f64 {
+            if fraction < 0. {
+                -1.
+            } else if fraction > 1. {
+                1.
+            } else {
+                0.
+            }
+        };
+        let (dx, dy) = (direction(x_fraction), -direction(y_fraction));
+        if dx == 0. && dy == 0. {
+            continue;
+        }
+        // The tip of the arrow sits on the edge, with its base inside the face
+        let tip = (
+            face_width * x_fraction.clamp(0., 1.),
+            -face_height * y_fraction.clamp(0., 1.),
+        );
+        let length = (dx * dx + dy * dy).sqrt();
+        let (ux, uy) = (dx / length, dy / length);
+        let base = (tip.0 - ux * SIZE, tip.1 - uy * SIZE);
+        let (px, py) = (-uy * SIZE / 2., ux * SIZE / 2.);
+        group.append(
+            node::element::Polygon::new()
+                .set(
+                    "points",
+                    format!(
+                        "{},{} {},{} {},{}",
+                        tip.0,
+                        tip.1,
+                        base.0 + px,
+                        base.1 + py,
+                        base.0 - px,
+                        base.1 - py
+                    ),
+                )
+                .set("fill", "dimgrey"),
+        );
     }
+    group
 }
 
 #[cfg(test)]
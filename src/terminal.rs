@@ -0,0 +1,108 @@
+/*!
+The `terminal` module describes how much colour a terminal can show.
+
+It is used with `Page::text_colours` to draw text output in the colours of each representation.
+
+```no_run
+# use plotlib::page::Page;
+# use plotlib::repr::Plot;
+# use plotlib::style::PointStyle;
+# use plotlib::terminal::Colours;
+# use plotlib::view::ContinuousView;
+let p = Plot::new(vec![(0., 1.), (1., 3.)]).point_style(PointStyle::new().colour("#DD3355"));
+let v = ContinuousView::new().add(p);
+let page = Page::single(&v).dimensions(60, 15).text_colours(Colours::detect());
+println!("{}", page.to_text().unwrap());
+```
+*/
+
+use std::env;
+use std::io::IsTerminal;
+
+/// The colour escape sequences which text output may use
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Colours {
+    /// Plain text with no escape sequences
+    #[default]
+    None,
+    /// The 16 standard ANSI colours
+    Ansi16,
+    /// The 256 colour palette of xterm
+    Ansi256,
+    /// 24-bit colour
+    TrueColour,
+}
+
+impl Colours {
+    /**
+    Work out what standard output can show from the environment.
+
+    Returns `Colours::None` if `NO_COLOR` is set or standard output is not a terminal,
+    and otherwise uses `COLORTERM` and `TERM` to choose the palette.
+    */
+    pub fn detect() -> Colours {
+        Colours::choose(
+            env::var("NO_COLOR").ok(),
+            std::io::stdout().is_terminal(),
+            env::var("COLORTERM").ok(),
+            env::var("TERM").ok(),
+        )
+    }
+
+    fn choose(
+        no_color: Option<String>,
+        is_terminal: bool,
+        colorterm: Option<String>,
+        term: Option<String>,
+    ) -> Colours {
+        if no_color.is_some_and(|v| !v.is_empty()) || !is_terminal {
+            return Colours::None;
+        }
+        if let Some("truecolor") | Some("24bit") = colorterm.as_deref() {
+            return Colours::TrueColour;
+        }
+        match term.as_deref() {
+            None | Some("dumb") => Colours::None,
+            Some(term) if term.contains("256") => Colours::Ansi256,
+            Some(_) => Colours::Ansi16,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_choose() {
+        let some = |s: &str| Some(s.to_string());
+        assert_eq!(
+            Colours::choose(None, true, some("truecolor"), some("xterm")),
+            Colours::TrueColour
+        );
+        assert_eq!(
+            Colours::choose(None, true, None, some("xterm-256color")),
+            Colours::Ansi256
+        );
+        assert_eq!(
+            Colours::choose(None, true, None, some("xterm")),
+            Colours::Ansi16
+        );
+        assert_eq!(
+            Colours::choose(None, true, None, some("dumb")),
+            Colours::None
+        );
+        assert_eq!(
+            Colours::choose(some("1"), true, some("truecolor"), None),
+            Colours::None
+        );
+        assert_eq!(
+            Colours::choose(some(""), true, None, some("xterm")),
+            Colours::Ansi16
+        );
+        assert_eq!(
+            Colours::choose(None, false, some("truecolor"), None),
+            Colours::None
+        );
+    }
+}
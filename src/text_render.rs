@@ -1,18 +1,21 @@
 //! A module for plotting graphs
 
-use std;
+use std::cmp::Ordering;
 use std::collections::HashMap;
 
 use crate::axis;
+use crate::colour::{self, Rgb};
+use crate::grid::GridLines;
 use crate::repr;
 use crate::style;
+use crate::terminal::Colours;
+use crate::utils;
 use crate::utils::PairWise;
 
 // Given a value like a tick label or a bin count,
 // calculate how far from the x-axis it should be plotted
 fn value_to_axis_cell_offset(value: f64, axis: &axis::ContinuousAxis, face_cells: u32) -> i32 {
-    let data_per_cell = (axis.max() - axis.min()) / f64::from(face_cells);
-    ((value - axis.min()) / data_per_cell).round() as i32
+    (axis.fraction(value) * f64::from(face_cells)).round() as i32
 }
 
 /// Given a list of ticks to display,
@@ -26,6 +29,14 @@ fn tick_offset_map(axis: &axis::ContinuousAxis, face_width: u32) -> HashMap<i32,
         .collect()
 }
 
+/// The cell offsets of the minor ticks of an axis
+fn minor_tick_offsets(axis: &axis::ContinuousAxis, face_width: u32) -> Vec<i32> {
+    axis.minor_ticks()
+        .iter()
+        .map(|&tick| value_to_axis_cell_offset(tick, axis, face_width))
+        .collect()
+}
+
 /// Given a histogram object,
 /// the total scale of the axis
 /// and the number of face cells to work with,
@@ -98,13 +109,13 @@ struct XAxisLabel {
 
 impl XAxisLabel {
     fn len(&self) -> usize {
-        self.text.len()
+        self.text.chars().count()
     }
 
     /// The number of cells the label will actually use
     /// We want this to always be an odd number
     fn footprint(&self) -> usize {
-        if self.len() % 2 == 0 {
+        if self.len().is_multiple_of(2) {
             self.len() + 1
         } else {
             self.len()
@@ -113,15 +124,18 @@ impl XAxisLabel {
 
     /// The offset, relative to the zero-point of the axis where the label should start to be drawn
     fn start_offset(&self) -> i32 {
-        self.offset as i32 - self.footprint() as i32 / 2
+        self.offset - self.footprint() as i32 / 2
     }
 }
 
-fn create_x_axis_labels(x_tick_map: &HashMap<i32, f64>) -> Vec<XAxisLabel> {
+fn create_x_axis_labels(
+    x_axis: &axis::ContinuousAxis,
+    x_tick_map: &HashMap<i32, f64>,
+) -> Vec<XAxisLabel> {
     let mut ls: Vec<_> = x_tick_map
         .iter()
         .map(|(&offset, &tick)| XAxisLabel {
-            text: tick.to_string(),
+            text: x_axis.tick_label(tick),
             offset,
         })
         .collect();
@@ -129,46 +143,75 @@ fn create_x_axis_labels(x_tick_map: &HashMap<i32, f64>) -> Vec<XAxisLabel> {
     ls
 }
 
-pub fn render_y_axis_strings(y_axis: &axis::ContinuousAxis, face_height: u32) -> (String, i32) {
+/// The index of the segment of a broken axis covering a cell offset, and the offset within it
+///
+/// Each segment is given with the offset of its zero and its size in cells.
+/// The zero of each segment after the first is the gap which separates it from the one before.
+fn segment_at(segments: &[(&axis::ContinuousAxis, u32, u32)], offset: u32) -> Option<(usize, i32)> {
+    segments
+        .iter()
+        .position(|&(_, base, size)| base <= offset && offset <= base + size)
+        .map(|index| (index, (offset - segments[index].1) as i32))
+}
+
+/// Create the strings for a y-axis in segments,
+/// each given with the row of its zero and its height in rows
+///
+/// The axis is labelled with the label of the first segment, and broken with a `~` at each gap.
+pub fn render_y_axis_strings(
+    segments: &[(&axis::ContinuousAxis, u32, u32)],
+    face_height: u32,
+) -> (String, i32) {
     // Get the strings and offsets we'll use for the y-axis
-    let y_tick_map = tick_offset_map(y_axis, face_height);
+    let y_tick_maps: Vec<_> = segments
+        .iter()
+        .map(|&(a, _, size)| tick_offset_map(a, size))
+        .collect();
+    let y_minor_ticks: Vec<_> = segments
+        .iter()
+        .map(|&(a, _, size)| minor_tick_offsets(a, size))
+        .collect();
 
     // Find a minimum size for the left gutter
-    let longest_y_label_width = y_tick_map
-        .values()
-        .map(|n| n.to_string().len())
+    let longest_y_label_width = segments
+        .iter()
+        .zip(&y_tick_maps)
+        .flat_map(|(&(a, _, _), map)| map.values().map(move |&n| a.tick_label(n).chars().count()))
         .max()
-        .expect("ERROR: There are no y-axis ticks");
+        .unwrap_or(0);
 
     let y_axis_label = format!(
         "{: ^width$}",
-        y_axis.get_label(),
+        segments.first().map_or("", |(a, _, _)| a.get_label()),
         width = face_height as usize + 1
     );
     let y_axis_label: Vec<_> = y_axis_label.chars().rev().collect();
 
-    // Generate a list of strings to label the y-axis
-    let y_label_strings: Vec<_> = (0..=face_height)
-        .map(|line| match y_tick_map.get(&(line as i32)) {
-            Some(v) => v.to_string(),
-            None => "".to_string(),
-        })
-        .collect();
-
-    // Generate a list of strings to tick the y-axis
-    let y_tick_strings: Vec<_> = (0..=face_height)
-        .map(|line| match y_tick_map.get(&(line as i32)) {
-            Some(_) => "-".to_string(),
-            None => " ".to_string(),
-        })
-        .collect();
-
-    // Generate a list of strings to be the y-axis line itself
-    let y_axis_line_strings: Vec<String> = std::iter::repeat('+')
-        .take(1)
-        .chain(std::iter::repeat('|').take(face_height as usize))
-        .map(|s| s.to_string())
-        .collect();
+    // Generate a list of strings to label and tick the y-axis, and to be the y-axis line itself
+    let mut y_label_strings = vec![];
+    let mut y_tick_strings = vec![];
+    let mut y_axis_line_strings = vec![];
+    for line in 0..=face_height {
+        let (label, tick, axis_line) = match segment_at(segments, line) {
+            Some((index, offset)) => {
+                let (label, tick) = match y_tick_maps[index].get(&offset) {
+                    Some(&v) => (segments[index].0.tick_label(v), "-"),
+                    None if y_minor_ticks[index].contains(&offset) => (String::new(), "'"),
+                    None => (String::new(), " "),
+                };
+                let axis_line = match (line, offset) {
+                    (0, _) => "+",
+                    (_, 0) => "~",
+                    _ => "|",
+                };
+                (label, tick, axis_line)
+            }
+            None => (String::new(), " ", "|"),
+        };
+        y_label_strings.push(label);
+        y_tick_strings.push(tick.to_string());
+        y_axis_line_strings.push(axis_line.to_string());
+    }
 
     let iter = y_axis_label
         .iter()
@@ -196,31 +239,110 @@ pub fn render_y_axis_strings(y_axis: &axis::ContinuousAxis, face_height: u32) ->
     (axis_string, longest_y_label_width as i32)
 }
 
-pub fn render_x_axis_strings(x_axis: &axis::ContinuousAxis, face_width: u32) -> (String, i32) {
+/// Create the strings for a y-axis drawn on the right of the face, mirroring `render_y_axis_strings`
+///
+/// Returns the strings and their width, with the axis line in the first column.
+pub fn render_secondary_y_axis_strings(
+    y_axis: &axis::ContinuousAxis,
+    face_height: u32,
+) -> (String, i32) {
+    let y_tick_map = tick_offset_map(y_axis, face_height);
+    let y_minor_ticks = minor_tick_offsets(y_axis, face_height);
+
+    let longest_y_label_width = y_tick_map
+        .values()
+        .map(|&n| y_axis.tick_label(n).chars().count())
+        .max()
+        .unwrap_or(0);
+
+    // The label reads downwards, from the top of the axis
+    let y_axis_label: Vec<char> = format!(
+        "{: ^width$}",
+        y_axis.get_label(),
+        width = face_height as usize + 1
+    )
+    .chars()
+    .collect();
+
+    let axis_string: Vec<String> = (0..=face_height)
+        .rev()
+        .zip(y_axis_label.iter())
+        .map(|(line, label_char)| {
+            let line = line as i32;
+            let (tick, tick_label) = match y_tick_map.get(&line) {
+                Some(&v) => ('-', y_axis.tick_label(v)),
+                None if y_minor_ticks.contains(&line) => ('\'', String::new()),
+                None => (' ', String::new()),
+            };
+            format!(
+                "{}{}{:<num_width$} {}",
+                if line == 0 { '+' } else { '|' },
+                tick,
+                tick_label,
+                label_char,
+                num_width = longest_y_label_width
+            )
+        })
+        .collect();
+
+    (axis_string.join("\n"), longest_y_label_width as i32 + 4)
+}
+
+/// Create the strings for an x-axis in segments,
+/// each given with the column of its zero and its width in columns
+///
+/// The axis is labelled with the label of the first segment, and broken with a `~` at each gap.
+pub fn render_x_axis_strings(
+    segments: &[(&axis::ContinuousAxis, u32, u32)],
+    face_width: u32,
+) -> (String, i32) {
     // Get the strings and offsets we'll use for the x-axis
-    let x_tick_map = tick_offset_map(x_axis, face_width as u32);
+    let x_tick_maps: Vec<_> = segments
+        .iter()
+        .map(|&(a, _, size)| tick_offset_map(a, size))
+        .collect();
 
     // Create a string which will be printed to give the x-axis tick marks
+    let x_minor_ticks: Vec<_> = segments
+        .iter()
+        .map(|&(a, _, size)| minor_tick_offsets(a, size))
+        .collect();
     let x_axis_tick_string: String = (0..=face_width)
-        .map(|cell| match x_tick_map.get(&(cell as i32)) {
-            Some(_) => '|',
+        .map(|cell| match segment_at(segments, cell) {
+            Some((index, offset)) => match x_tick_maps[index].get(&offset) {
+                Some(_) => '|',
+                None if x_minor_ticks[index].contains(&offset) => '\'',
+                None => ' ',
+            },
             None => ' ',
         })
         .collect();
 
     // Create a string which will be printed to give the x-axis labels
-    let x_labels = create_x_axis_labels(&x_tick_map);
+    let mut x_labels: Vec<_> = segments
+        .iter()
+        .zip(&x_tick_maps)
+        .flat_map(|(&(a, base, _), map)| {
+            create_x_axis_labels(a, map)
+                .into_iter()
+                .map(move |label| XAxisLabel {
+                    offset: label.offset + base as i32,
+                    ..label
+                })
+        })
+        .collect();
+    x_labels.sort_by_key(|l| l.offset);
     let start_offset = x_labels
         .iter()
         .map(|label| label.start_offset())
         .min()
-        .expect("ERROR: Could not compute start offset of x-axis");
+        .unwrap_or(0);
 
     // This string will be printed, starting at start_offset relative to the x-axis zero cell
     let mut x_axis_label_string = "".to_string();
-    for label in (&x_labels).iter() {
+    for label in x_labels.iter() {
         let spaces_to_append =
-            label.start_offset() - start_offset - x_axis_label_string.len() as i32;
+            label.start_offset() - start_offset - x_axis_label_string.chars().count() as i32;
         if spaces_to_append.is_positive() {
             for _ in 0..spaces_to_append {
                 x_axis_label_string.push(' ');
@@ -235,14 +357,17 @@ pub fn render_x_axis_strings(x_axis: &axis::ContinuousAxis, face_width: u32) ->
     }
 
     // Generate a list of strings to be the y-axis line itself
-    let x_axis_line_string: String = std::iter::repeat('+')
-        .take(1)
-        .chain(std::iter::repeat('-').take(face_width as usize))
+    let x_axis_line_string: String = (0..=face_width)
+        .map(|cell| match (cell, segment_at(segments, cell)) {
+            (0, _) => '+',
+            (_, Some((_, 0))) => '~',
+            _ => '-',
+        })
         .collect();
 
     let x_axis_label = format!(
         "{: ^width$}",
-        x_axis.get_label(),
+        segments.first().map_or("", |(a, _, _)| a.get_label()),
         width = face_width as usize
     );
 
@@ -271,6 +396,267 @@ pub fn render_x_axis_strings(x_axis: &axis::ContinuousAxis, face_width: u32) ->
     (x_axis_string, start_offset)
 }
 
+/// The cell offset of the middle of each category along a categorical axis
+fn categorical_tick_offsets(x_axis: &axis::CategoricalAxis, face_width: u32) -> Vec<i32> {
+    let space_per_tick = f64::from(face_width) / x_axis.ticks().len() as f64;
+    (0..x_axis.ticks().len())
+        .map(|i| {
+            let offset = ((i as f64 + 0.5) * space_per_tick).round() as i32;
+            offset.clamp(1, face_width.max(1) as i32)
+        })
+        .collect()
+}
+
+pub fn render_categorical_x_axis_strings(
+    x_axis: &axis::CategoricalAxis,
+    face_width: u32,
+) -> String {
+    let tick_offsets = categorical_tick_offsets(x_axis, face_width);
+
+    let x_axis_line_string: String = std::iter::once('+')
+        .chain(std::iter::repeat_n('-', face_width as usize))
+        .collect();
+
+    let x_axis_tick_string: String = (0..=face_width as i32)
+        .map(|cell| {
+            if tick_offsets.contains(&cell) {
+                '|'
+            } else {
+                ' '
+            }
+        })
+        .collect();
+
+    // Shorten the labels so that neighbouring ones don't run into each other
+    let space_per_tick = face_width as usize / x_axis.ticks().len().max(1);
+    let max_label_width = space_per_tick.saturating_sub(1).max(1);
+    let mut label_cells = vec![' '; face_width as usize + 1];
+    for (tick, &offset) in x_axis.ticks().iter().zip(&tick_offsets) {
+        let label: Vec<char> = tick.chars().take(max_label_width).collect();
+        let start = offset - label.len() as i32 / 2;
+        for (cell, &c) in (start..).zip(&label) {
+            if cell >= 0 && (cell as usize) < label_cells.len() {
+                label_cells[cell as usize] = c;
+            }
+        }
+    }
+    let x_axis_label_string: String = label_cells.into_iter().collect();
+
+    let x_axis_label = format!(
+        "{: ^width$}",
+        x_axis.get_label(),
+        width = face_width as usize
+    );
+
+    format!(
+        "{}\n{}\n{}\n{}",
+        x_axis_line_string, x_axis_tick_string, x_axis_label_string, x_axis_label
+    )
+}
+
+/// The first and last cell offsets covered by a bar or box in the category with the given label
+fn category_columns(label: &str, x_axis: &axis::CategoricalAxis, face_width: u32) -> (i32, i32) {
+    let tick_index = x_axis.ticks().iter().position(|t| t == label).unwrap_or(0);
+    let centre = categorical_tick_offsets(x_axis, face_width)[tick_index];
+    let space_per_tick = f64::from(face_width) / x_axis.ticks().len() as f64;
+    // Like the SVG, boxes take up half of the space for their category
+    let box_width = ((space_per_tick / 2.).round() as i32).max(1);
+    let left = centre - (box_width - 1) / 2;
+    (left, left + box_width - 1)
+}
+
+/// Write a character onto a face at the given cell offsets, ignoring any off the face
+fn put(face: &mut [Vec<char>], column: i32, line: i32, c: char) {
+    let face_height = face.len() as i32;
+    if line < 1 || line > face_height || column < 1 {
+        return;
+    }
+    if let Some(cell) = face[(face_height - line) as usize].get_mut(column as usize - 1) {
+        *cell = c;
+    }
+}
+
+fn face_to_string(face: &[Vec<char>]) -> String {
+    face.iter()
+        .map(|line| line.iter().collect())
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Given a list of points,
+/// the x ands y-axes
+/// and the face height and width,
+/// create the string marking the edge of the face with an arrow for each point outside of it
+pub fn render_face_edge_markers(
+    s: &[(f64, f64)],
+    x_axis: &axis::ContinuousAxis,
+    y_axis: &axis::ContinuousAxis,
+    face_width: u32,
+    face_height: u32,
+) -> String {
+    let mut face = vec![vec![' '; face_width as usize]; face_height as usize];
+    for &(x, y) in s {
+        let (column, line) = (
+            value_to_axis_cell_offset(x, x_axis, face_width),
+            value_to_axis_cell_offset(y, y_axis, face_height),
+        );
+        let (x_fraction, y_fraction) = (x_axis.fraction(x), y_axis.fraction(y));
+        let marker = match (
+            x_fraction.partial_cmp(&0.),
+            x_fraction.partial_cmp(&1.),
+            y_fraction.partial_cmp(&0.),
+            y_fraction.partial_cmp(&1.),
+        ) {
+            (None, ..) | (_, _, None, _) => continue,
+            (Some(Ordering::Less), _, Some(Ordering::Less), _) => '◣',
+            (Some(Ordering::Less), _, _, Some(Ordering::Greater)) => '◤',
+            (_, Some(Ordering::Greater), Some(Ordering::Less), _) => '◢',
+            (_, Some(Ordering::Greater), _, Some(Ordering::Greater)) => '◥',
+            (Some(Ordering::Less), ..) => '◀',
+            (_, Some(Ordering::Greater), ..) => '▶',
+            (.., Some(Ordering::Less), _) => '▼',
+            (.., Some(Ordering::Greater)) => '▲',
+            _ => continue,
+        };
+        put(
+            &mut face,
+            column.clamp(1, face_width as i32),
+            line.clamp(1, face_height as i32),
+            marker,
+        );
+    }
+    face_to_string(&face)
+}
+
+/// Given the positions of the lines of a grid
+/// and the face height and width,
+/// create the string to be drawn under the representations on the face.
+///
+/// Major lines are dashed, with `┼` where they cross, and minor lines are dotted.
+/// Lines which would fall on the axes are left out.
+pub fn render_face_grid(lines: &GridLines, face_width: u32, face_height: u32) -> String {
+    let mut face = vec![vec![' '; face_width as usize]; face_height as usize];
+    let columns = |fractions: &[f64]| -> Vec<i32> {
+        fractions
+            .iter()
+            .map(|f| (f * f64::from(face_width)).round() as i32)
+            .collect()
+    };
+    let rows = |fractions: &[f64]| -> Vec<i32> {
+        fractions
+            .iter()
+            .map(|f| (f * f64::from(face_height)).round() as i32)
+            .collect()
+    };
+
+    for line in rows(&lines.minor_y) {
+        for column in 1..=face_width as i32 {
+            put(&mut face, column, line, '·');
+        }
+    }
+    for column in columns(&lines.minor_x) {
+        for line in 1..=face_height as i32 {
+            put(&mut face, column, line, '·');
+        }
+    }
+    let major_rows = rows(&lines.y);
+    for &line in &major_rows {
+        for column in 1..=face_width as i32 {
+            put(&mut face, column, line, '┄');
+        }
+    }
+    for column in columns(&lines.x) {
+        for line in 1..=face_height as i32 {
+            let c = if major_rows.contains(&line) {
+                '┼'
+            } else {
+                '┆'
+            };
+            put(&mut face, column, line, c);
+        }
+    }
+
+    face_to_string(&face)
+}
+
+/// Given a bar chart's value and label,
+/// the x ands y-axes
+/// and the face height and width,
+/// create the strings to be drawn as the face
+pub fn render_face_bar(
+    value: f64,
+    label: &str,
+    x_axis: &axis::CategoricalAxis,
+    y_axis: &axis::ContinuousAxis,
+    face_width: u32,
+    face_height: u32,
+) -> String {
+    let mut face = vec![vec![' '; face_width as usize]; face_height as usize];
+    let (left, right) = category_columns(label, x_axis, face_width);
+
+    let top = value_to_axis_cell_offset(value, y_axis, face_height);
+    let base = value_to_axis_cell_offset(0., y_axis, face_height);
+    for line in top.min(base)..=top.max(base) {
+        for column in left..=right {
+            if line == top || line == base {
+                put(&mut face, column, line, '-');
+            } else if column == left || column == right {
+                put(&mut face, column, line, '|');
+            }
+        }
+    }
+    face_to_string(&face)
+}
+
+/// Given the data of a box plot and its label,
+/// the x ands y-axes
+/// and the face height and width,
+/// create the strings to be drawn as the face
+pub fn render_face_box(
+    data: &[f64],
+    label: &str,
+    x_axis: &axis::CategoricalAxis,
+    y_axis: &axis::ContinuousAxis,
+    face_width: u32,
+    face_height: u32,
+) -> String {
+    let mut face = vec![vec![' '; face_width as usize]; face_height as usize];
+    if data.is_empty() {
+        return face_to_string(&face);
+    }
+    let (left, right) = category_columns(label, x_axis, face_width);
+    let centre = (left + right) / 2;
+
+    let offset = |v: f64| value_to_axis_cell_offset(v, y_axis, face_height);
+    let (q1, median, q3) = utils::quartiles(data);
+    let (min, max) = utils::range(data);
+    let (q1, median, q3, min, max) = (
+        offset(q1),
+        offset(median),
+        offset(q3),
+        offset(min),
+        offset(max),
+    );
+
+    // The whiskers
+    for line in (min..q1).chain(q3 + 1..=max) {
+        put(&mut face, centre, line, '|');
+    }
+    // The box, with the median across it
+    for line in q1..=q3 {
+        for column in left..=right {
+            if line == median {
+                put(&mut face, column, line, '=');
+            } else if line == q1 || line == q3 {
+                put(&mut face, column, line, '-');
+            } else if column == left || column == right {
+                put(&mut face, column, line, '|');
+            }
+        }
+    }
+    face_to_string(&face)
+}
+
 /// Given a histogram,
 /// the x ands y-axes
 /// and the face height and width,
@@ -282,16 +668,36 @@ pub fn render_face_bars(
     face_width: u32,
     face_height: u32,
 ) -> String {
-    let bound_cells = bound_cell_offsets(h, x_axis, face_width);
+    let mut bound_cells = bound_cell_offsets(h, x_axis, face_width);
+    // On a reversed x-axis the bins run from right to left
+    if x_axis.is_reversed() {
+        bound_cells.reverse();
+    }
+    let values = h.get_values();
+    let bin_value = |b: i32| {
+        if x_axis.is_reversed() {
+            values[values.len() - 1 - b as usize]
+        } else {
+            values[b as usize]
+        }
+    };
 
     let cell_bins = bins_for_cells(&bound_cells, face_width);
 
     // counts per bin converted to rows per column
+    // On a reversed y-axis the bars hang from the top, so are drawn upside down and flipped
     let cell_heights: Vec<_> = cell_bins
         .iter()
         .map(|&bin| match bin {
             None => 0,
-            Some(b) => value_to_axis_cell_offset(h.get_values()[b as usize], y_axis, face_height),
+            Some(b) => {
+                let offset = value_to_axis_cell_offset(bin_value(b), y_axis, face_height);
+                match (y_axis.is_reversed(), offset >= face_height as i32) {
+                    (false, _) => offset,
+                    (true, true) => 0,
+                    (true, false) => face_height as i32 + 1 - offset,
+                }
+            }
         })
         .collect();
 
@@ -341,7 +747,9 @@ pub fn render_face_bars(
         }
         face_strings.push(line_string);
     }
-    let face_strings: Vec<String> = face_strings.iter().rev().cloned().collect();
+    if !y_axis.is_reversed() {
+        face_strings.reverse();
+    }
     face_strings.join("\n")
 }
 
@@ -389,87 +797,495 @@ pub fn render_face_points(
     face_strings.join("\n")
 }
 
-/// Given two 'rectangular' strings, overlay the second on the first offset by `x` and `y`
-pub fn overlay(under: &str, over: &str, x: i32, y: i32) -> String {
-    let split_under: Vec<_> = under.split('\n').collect();
-    let under_width = split_under.iter().map(|s| s.len()).max().unwrap();
-    let under_height = split_under.len();
-
-    let split_over: Vec<String> = over.split('\n').map(|s| s.to_string()).collect();
-    let over_width = split_over.iter().map(|s| s.len()).max().unwrap();
-
-    // Take `over` and pad it so that it matches `under`'s dimensions
-
-    // Trim/add lines at beginning
-    let split_over: Vec<String> = if y.is_negative() {
-        split_over.iter().skip(y.abs() as usize).cloned().collect()
-    } else if y.is_positive() {
-        (0..y)
-            .map(|_| (0..over_width).map(|_| ' ').collect())
-            .chain(split_over.iter().map(|s| s.to_string()))
-            .collect()
+/// Choose the character which best shows a line segment with the given slope,
+/// measured in cells up per cell across
+fn line_glyph(slope: f64) -> char {
+    let steepness = slope.abs();
+    if steepness < 0.4 {
+        '-'
+    } else if steepness > 2.5 || steepness.is_nan() {
+        '|'
+    } else if slope > 0. {
+        '/'
     } else {
-        split_over
-    };
+        '\\'
+    }
+}
 
-    // Trim/add chars at beginning
-    let split_over: Vec<String> = if x.is_negative() {
-        split_over
-            .iter()
-            .map(|l| l.chars().skip(x.abs() as usize).collect())
-            .collect()
-    } else if x.is_positive() {
-        split_over
-            .iter()
-            .map(|s| (0..x).map(|_| ' ').chain(s.chars()).collect())
-            .collect()
-    } else {
-        split_over
+/// Clip the segment between two points to a rectangle, using Liang-Barsky
+pub fn clip_segment(
+    (x1, y1): (f64, f64),
+    (x2, y2): (f64, f64),
+    (x_min, y_min): (f64, f64),
+    (x_max, y_max): (f64, f64),
+) -> Option<((f64, f64), (f64, f64))> {
+    let (dx, dy) = (x2 - x1, y2 - y1);
+    let (mut t0, mut t1) = (0_f64, 1_f64);
+    for &(p, q) in &[
+        (-dx, x1 - x_min),
+        (dx, x_max - x1),
+        (-dy, y1 - y_min),
+        (dy, y_max - y1),
+    ] {
+        if p == 0. {
+            if q < 0. {
+                return None;
+            }
+        } else if p < 0. {
+            t0 = t0.max(q / p);
+        } else {
+            t1 = t1.min(q / p);
+        }
+    }
+    if t0 > t1 {
+        return None;
+    }
+    Some(((x1 + t0 * dx, y1 + t0 * dy), (x1 + t1 * dx, y1 + t1 * dy)))
+}
+
+/// Visit every cell on the line between two cells, using Bresenham's algorithm
+pub fn bresenham<F>(start: (i32, i32), end: (i32, i32), mut visit: F)
+where
+    F: FnMut(i32, i32),
+{
+    let (mut x, mut y) = start;
+    let (dx, dy) = ((end.0 - x).abs(), -(end.1 - y).abs());
+    let (step_x, step_y) = ((end.0 - x).signum(), (end.1 - y).signum());
+    let mut error = dx + dy;
+    loop {
+        visit(x, y);
+        if (x, y) == end {
+            break;
+        }
+        let doubled = 2 * error;
+        if doubled >= dy {
+            error += dy;
+            x += step_x;
+        }
+        if doubled <= dx {
+            error += dx;
+            y += step_y;
+        }
+    }
+}
+
+/// Given a line plot,
+/// the x ands y-axes
+/// and the face height and width,
+/// create the strings to be drawn as the face
+///
+/// Each segment is rasterised with `bresenham`,
+/// using a character which follows its slope.
+pub fn render_face_line(
+    s: &[(f64, f64)],
+    x_axis: &axis::ContinuousAxis,
+    y_axis: &axis::ContinuousAxis,
+    face_width: u32,
+    face_height: u32,
+) -> String {
+    // Cell offsets before rounding, which match those of `render_face_points`
+    let cell_offset = |value: f64, axis: &axis::ContinuousAxis, face_cells: u32| {
+        axis.fraction(value) * f64::from(face_cells)
     };
+    let points: Vec<_> = s
+        .iter()
+        .filter(|(x, y)| x.is_finite() && y.is_finite())
+        .map(|&(x, y)| {
+            (
+                cell_offset(x, x_axis, face_width),
+                cell_offset(y, y_axis, face_height),
+            )
+        })
+        .collect();
 
-    // pad out end of vector
-    let over_width = split_over.iter().map(|s| s.len()).max().unwrap();
-    let over_height = split_over.len();
-    let lines_deficit = under_height as i32 - over_height as i32;
-    let split_over: Vec<String> = if lines_deficit.is_positive() {
-        let new_lines: Vec<String> = (0..lines_deficit)
-            .map(|_| (0..over_width).map(|_| ' ').collect::<String>())
-            .collect();
-        let mut temp = split_over;
-        for new_line in new_lines {
-            temp.push(new_line);
+    let mut face = vec![vec![' '; face_width as usize]; face_height as usize];
+    let bottom_left = (0.5, 0.5);
+    let top_right = (f64::from(face_width) + 0.49, f64::from(face_height) + 0.49);
+    for (&start, &end) in points.pairwise() {
+        let glyph = line_glyph((end.1 - start.1) / (end.0 - start.0));
+        let (start, end) = match clip_segment(start, end, bottom_left, top_right) {
+            Some(clipped) => clipped,
+            None => continue,
+        };
+        let start = (start.0.round() as i32, start.1.round() as i32);
+        let end = (end.0.round() as i32, end.1.round() as i32);
+        bresenham(start, end, |x, y| {
+            // Cell offsets count from 1 at the bottom left of the face
+            if x >= 1 && x <= face_width as i32 && y >= 1 && y <= face_height as i32 {
+                face[(face_height as i32 - y) as usize][(x - 1) as usize] = glyph;
+            }
+        });
+    }
+    face.iter()
+        .map(|line| line.iter().collect())
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// The height of a line through the given points where it passes `x`,
+/// taken from the first segment which spans it, or None if none do
+pub(crate) fn interpolate(points: &[(f64, f64)], x: f64) -> Option<f64> {
+    points
+        .pairwise()
+        .find(|(a, b)| a.0.min(b.0) <= x && x <= a.0.max(b.0))
+        .map(|(&(x0, y0), &(x1, y1))| {
+            if x0 == x1 {
+                y1
+            } else {
+                y0 + (y1 - y0) * (x - x0) / (x1 - x0)
+            }
+        })
+}
+
+/// Given the upper and lower edges of an area,
+/// the x ands y-axes
+/// and the face height and width,
+/// create the strings to be drawn as the face
+///
+/// Each column is shaded with the given character between where the two edges cross it.
+pub fn render_face_area(
+    upper: &[(f64, f64)],
+    lower: &[(f64, f64)],
+    x_axis: &axis::ContinuousAxis,
+    y_axis: &axis::ContinuousAxis,
+    face_width: u32,
+    face_height: u32,
+    shade: char,
+) -> String {
+    let upper = area_cells(upper, x_axis, y_axis, face_width, face_height);
+    let lower = area_cells(lower, x_axis, y_axis, face_width, face_height);
+    shade_columns(&upper, &lower, face_width, face_height, shade, 0)
+}
+
+/// Like `render_face_area`, for a layer of a stacked area which sits on another layer.
+///
+/// Shading starts from the line above the lower edge, which is left to the layer below.
+pub fn render_face_stacked_layer(
+    upper: &[(f64, f64)],
+    lower: &[(f64, f64)],
+    x_axis: &axis::ContinuousAxis,
+    y_axis: &axis::ContinuousAxis,
+    face_width: u32,
+    face_height: u32,
+    shade: char,
+) -> String {
+    let upper = area_cells(upper, x_axis, y_axis, face_width, face_height);
+    let lower = area_cells(lower, x_axis, y_axis, face_width, face_height);
+    shade_columns(&upper, &lower, face_width, face_height, shade, 1)
+}
+
+/// The unrounded cell offsets of the points along an edge of an area
+fn area_cells(
+    s: &[(f64, f64)],
+    x_axis: &axis::ContinuousAxis,
+    y_axis: &axis::ContinuousAxis,
+    face_width: u32,
+    face_height: u32,
+) -> Vec<(f64, f64)> {
+    s.iter()
+        .map(|&(x, y)| {
+            (
+                x_axis.fraction(x) * f64::from(face_width),
+                y_axis.fraction(y) * f64::from(face_height),
+            )
+        })
+        .filter(|(x, y)| x.is_finite() && y.is_finite())
+        .collect()
+}
+
+/// Shade each column between where the two edges cross it,
+/// starting `skip` lines above the lower edge
+fn shade_columns(
+    upper: &[(f64, f64)],
+    lower: &[(f64, f64)],
+    face_width: u32,
+    face_height: u32,
+    shade: char,
+    skip: i32,
+) -> String {
+    let mut face = vec![vec![' '; face_width as usize]; face_height as usize];
+    for column in 1..=face_width as i32 {
+        let x = f64::from(column);
+        if let (Some(a), Some(b)) = (interpolate(upper, x), interpolate(lower, x)) {
+            let (low, high) = (a.min(b).round() as i32, a.max(b).round() as i32);
+            for line in (low + skip).max(1)..=high.min(face_height as i32) {
+                put(&mut face, column, line, shade);
+            }
         }
-        temp
-    } else {
-        split_over
+    }
+    face_to_string(&face)
+}
+
+/// Given the ends of some error bars,
+/// the x ands y-axes
+/// and the face height and width,
+/// create the strings to be drawn as the face
+///
+/// Bars are drawn with box-drawing lines, capped with a tee at each end
+/// unless the style's cap width is zero, and crossing where they meet.
+pub fn render_face_error_bars(
+    bars: &[repr::ErrorBar],
+    x_axis: &axis::ContinuousAxis,
+    y_axis: &axis::ContinuousAxis,
+    face_width: u32,
+    face_height: u32,
+    style: &style::ErrorBarStyle,
+) -> String {
+    let capped = style.get_cap_width() > 0.;
+    let mut face = vec![vec![' '; face_width as usize]; face_height as usize];
+    for &((x1, y1), (x2, y2)) in bars {
+        let horizontal = y1 == y2;
+        let (axis, face_cells, start, end) = if horizontal {
+            (x_axis, face_width, x1, x2)
+        } else {
+            (y_axis, face_height, y1, y2)
+        };
+        let (fixed, fixed_axis, fixed_cells) = if horizontal {
+            (y1, y_axis, face_height)
+        } else {
+            (x1, x_axis, face_width)
+        };
+        if !(start.is_finite() && end.is_finite() && fixed.is_finite()) {
+            continue;
+        }
+        let fixed = value_to_axis_cell_offset(fixed, fixed_axis, fixed_cells);
+        let start = value_to_axis_cell_offset(start, axis, face_cells);
+        let end = value_to_axis_cell_offset(end, axis, face_cells);
+        // The ends are swapped if the axis is reversed
+        let (low, high) = (start.min(end), start.max(end));
+        let (body, low_cap, high_cap) = match (horizontal, capped) {
+            (true, true) => ('─', '├', '┤'),
+            (true, false) => ('─', '─', '─'),
+            (false, true) => ('│', '┴', '┬'),
+            (false, false) => ('│', '│', '│'),
+        };
+        // Only the cells on the face are visited, however long the bar
+        for offset in low.max(0)..=high.min(face_cells as i32 + 1) {
+            let glyph = if offset == low {
+                low_cap
+            } else if offset == high {
+                high_cap
+            } else {
+                body
+            };
+            let (column, line) = if horizontal {
+                (offset, fixed)
+            } else {
+                (fixed, offset)
+            };
+            if line < 1 || line > face_height as i32 || column < 1 || column > face_width as i32 {
+                continue;
+            }
+            let cell = &mut face[(face_height as i32 - line) as usize][column as usize - 1];
+            *cell = if *cell == ' ' || *cell == glyph {
+                glyph
+            } else {
+                '┼'
+            };
+        }
+    }
+    face_to_string(&face)
+}
+
+/// The escape sequence which returns to the terminal's default colour
+const RESET: &str = "\x1b[0m";
+
+/// A single character of text output, along with the escape sequence which colours it
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Cell<'a> {
+    escape: &'a str,
+    glyph: char,
+}
+
+/// Split a line of text into the characters which take up space,
+/// keeping any colour escape sequence with the character it applies to
+fn split_cells(line: &str) -> Vec<Cell<'_>> {
+    let mut cells = vec![];
+    let mut rest = line;
+    while let Some(glyph) = rest.chars().next() {
+        if glyph == '\x1b' {
+            let end = rest.find('m').map_or(rest.len(), |i| i + 1);
+            let escape = &rest[..end];
+            rest = &rest[end..];
+            if escape == RESET {
+                continue;
+            }
+            if let Some(glyph) = rest.chars().next() {
+                cells.push(Cell { escape, glyph });
+                rest = &rest[glyph.len_utf8()..];
+            }
+        } else {
+            cells.push(Cell { escape: "", glyph });
+            rest = &rest[glyph.len_utf8()..];
+        }
+    }
+    cells
+}
+
+fn join_cells(cells: &[Cell]) -> String {
+    let mut line = String::new();
+    for cell in cells {
+        line.push_str(cell.escape);
+        line.push(cell.glyph);
+        if !cell.escape.is_empty() {
+            line.push_str(RESET);
+        }
+    }
+    line
+}
+
+/// The number of characters a line of text takes up, ignoring colour escape sequences
+pub fn display_width(line: &str) -> usize {
+    split_cells(line).len()
+}
+
+/// Given two 'rectangular' strings, overlay the second on the first offset by `x` and `y`
+///
+/// Spaces in the second string are transparent.
+pub fn overlay(under: &str, over: &str, x: i32, y: i32) -> String {
+    let mut out: Vec<Vec<Cell>> = under.split('\n').map(split_cells).collect();
+
+    for (row, line) in (y..).zip(over.split('\n')) {
+        if row < 0 || row as usize >= out.len() {
+            continue;
+        }
+        let out_line = &mut out[row as usize];
+        for (column, cell) in (x..).zip(split_cells(line)) {
+            if column < 0 || column as usize >= out_line.len() || cell.glyph == ' ' {
+                continue;
+            }
+            let under_cell = &mut out_line[column as usize];
+            *under_cell = match (under_cell.glyph as u32, cell.glyph as u32) {
+                // Braille patterns are merged so that neither loses its dots
+                (0x2800..=0x28FF, 0x2800..=0x28FF) => Cell {
+                    glyph: std::char::from_u32(under_cell.glyph as u32 | cell.glyph as u32)
+                        .unwrap_or(cell.glyph),
+                    ..cell
+                },
+                _ => cell,
+            };
+        }
+    }
+
+    out.iter()
+        .map(|cells| join_cells(cells))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// The escape sequence which draws text in a CSS colour, if it can be shown.
+///
+/// Black is left as the terminal's default colour,
+/// so that it stays visible on terminals with a dark background.
+fn colour_escape(colour: &str, colours: Colours) -> Option<String> {
+    let Rgb(r, g, b) = colour::parse(colour)?;
+    if (r, g, b) == (0, 0, 0) {
+        return None;
+    }
+    match colours {
+        Colours::None => None,
+        Colours::TrueColour => Some(format!("\x1b[38;2;{};{};{}m", r, g, b)),
+        Colours::Ansi256 => Some(format!("\x1b[38;5;{}m", ansi_256(Rgb(r, g, b)))),
+        Colours::Ansi16 => Some(format!("\x1b[{}m", ansi_16(Rgb(r, g, b)))),
+    }
+}
+
+/// The squared distance between two colours
+fn distance(a: Rgb, b: Rgb) -> i32 {
+    let d = |x: u8, y: u8| i32::from(x) - i32::from(y);
+    d(a.0, b.0).pow(2) + d(a.1, b.1).pow(2) + d(a.2, b.2).pow(2)
+}
+
+/// The index of the closest colour in xterm's 256 colour palette
+fn ansi_256(rgb: Rgb) -> u8 {
+    // The 6×6×6 colour cube starting at 16
+    const LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+    let nearest_level = |v: u8| {
+        (0..6)
+            .min_by_key(|&i| (i32::from(LEVELS[i]) - i32::from(v)).abs())
+            .unwrap()
     };
+    let (r, g, b) = (
+        nearest_level(rgb.0),
+        nearest_level(rgb.1),
+        nearest_level(rgb.2),
+    );
+    let cube = Rgb(LEVELS[r], LEVELS[g], LEVELS[b]);
+    let cube_index = 16 + 36 * r + 6 * g + b;
 
-    // pad out end of each line
-    let line_width_deficit = under_width as i32 - over_width as i32;
-    let split_over: Vec<String> = if line_width_deficit.is_positive() {
-        split_over
-            .iter()
-            .map(|l| {
-                l.chars()
-                    .chain((0..line_width_deficit).map(|_| ' '))
-                    .collect()
-            })
-            .collect()
+    // The greyscale ramp from 232 to 255
+    let mean = (u32::from(rgb.0) + u32::from(rgb.1) + u32::from(rgb.2)) / 3;
+    let grey_step = ((mean.max(8) - 8) / 10).min(23) as u8;
+    let grey_level = 8 + 10 * grey_step;
+    let grey = Rgb(grey_level, grey_level, grey_level);
+
+    if distance(grey, rgb) < distance(cube, rgb) {
+        232 + grey_step
     } else {
-        split_over
-    };
+        cube_index as u8
+    }
+}
 
-    // Now that the dimensions match, overlay them
-    let mut out: Vec<String> = vec![];
-    for (l, ol) in split_under.iter().zip(split_over.iter()) {
-        let mut new_line = "".to_string();
-        for (c, oc) in l.chars().zip(ol.chars()) {
-            new_line.push(if oc == ' ' { c } else { oc });
-        }
-        out.push(new_line);
+/// The foreground code of the closest of the 16 standard colours
+fn ansi_16(rgb: Rgb) -> u8 {
+    // The default VGA palette, in the order of the codes 30-37 and then 90-97
+    const PALETTE: [Rgb; 16] = [
+        Rgb(0, 0, 0),
+        Rgb(170, 0, 0),
+        Rgb(0, 170, 0),
+        Rgb(170, 85, 0),
+        Rgb(0, 0, 170),
+        Rgb(170, 0, 170),
+        Rgb(0, 170, 170),
+        Rgb(170, 170, 170),
+        Rgb(85, 85, 85),
+        Rgb(255, 85, 85),
+        Rgb(85, 255, 85),
+        Rgb(255, 255, 85),
+        Rgb(85, 85, 255),
+        Rgb(255, 85, 255),
+        Rgb(85, 255, 255),
+        Rgb(255, 255, 255),
+    ];
+    let index = (0..16).min_by_key(|&i| distance(PALETTE[i], rgb)).unwrap() as u8;
+    if index < 8 {
+        30 + index
+    } else {
+        90 + index - 8
     }
+}
 
-    out.join("\n")
+/// Draw every character of a face in a CSS colour
+pub fn colourise(face: &str, colour: &str, colours: Colours) -> String {
+    let escape = match colour_escape(colour, colours) {
+        Some(escape) => escape,
+        None => return face.to_string(),
+    };
+    face.split('\n')
+        .map(|line| {
+            let cells: Vec<Cell> = split_cells(line)
+                .into_iter()
+                .map(|cell| match cell.glyph {
+                    ' ' => cell,
+                    _ => Cell {
+                        escape: &escape,
+                        ..cell
+                    },
+                })
+                .collect();
+            join_cells(&cells)
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Overlay each layer of a face in turn, ignoring their colours
+pub fn flatten_layers(layers: &[(String, String)], face_width: u32, face_height: u32) -> String {
+    layers
+        .iter()
+        .fold(empty_face(face_width, face_height), |face, (layer, _)| {
+            overlay(&face, layer, 0, 0)
+        })
 }
 
 pub fn empty_face(width: u32, height: u32) -> String {
@@ -486,9 +1302,9 @@ mod tests {
     #[test]
     fn test_bins_for_cells() {
         let face_width = 10;
-        let n = i32::max_value();
+        let n = i32::MAX;
         let run_bins_for_cells = |bound_cell_offsets: &[i32]| -> Vec<_> {
-            bins_for_cells(&bound_cell_offsets, face_width)
+            bins_for_cells(bound_cell_offsets, face_width)
                 .iter()
                 .map(|&a| a.unwrap_or(n))
                 .collect()
@@ -581,7 +1397,7 @@ mod tests {
     fn test_render_y_axis_strings() {
         let y_axis = axis::ContinuousAxis::new(0.0, 10.0, 6);
 
-        let (y_axis_string, longest_y_label_width) = render_y_axis_strings(&y_axis, 10);
+        let (y_axis_string, longest_y_label_width) = render_y_axis_strings(&[(&y_axis, 0, 10)], 10);
 
         assert!(y_axis_string.contains(&"0".to_string()));
         assert!(y_axis_string.contains(&"6".to_string()));
@@ -589,11 +1405,48 @@ mod tests {
         assert_eq!(longest_y_label_width, 2);
     }
 
+    #[test]
+    fn test_render_broken_axis_strings() {
+        let lower = axis::ContinuousAxis::new(0.0, 4.0, 3);
+        let upper = axis::ContinuousAxis::new(90.0, 100.0, 3);
+        let segments = [(&lower, 0, 4), (&upper, 5, 5)];
+
+        let (y_axis_string, longest_y_label_width) = render_y_axis_strings(&segments, 10);
+        let lines: Vec<&str> = y_axis_string.lines().collect();
+        assert_eq!(longest_y_label_width, 3);
+        assert_eq!(lines[0], "  100-|");
+        assert_eq!(lines[5], "   90-~");
+        assert_eq!(lines[6], "    4-|");
+        assert_eq!(lines[10], "    0-+");
+
+        let (x_axis_string, _) = render_x_axis_strings(&segments, 10);
+        let lines: Vec<&str> = x_axis_string.lines().collect();
+        assert_eq!(lines[0], "+----~-----");
+        assert_eq!(lines[1], "| | ||  | |");
+    }
+
+    #[test]
+    fn test_render_secondary_y_axis_strings() {
+        let y_axis = axis::ContinuousAxis::new(0.0, 10.0, 6).label("y2".to_string());
+
+        let (y_axis_string, width) = render_secondary_y_axis_strings(&y_axis, 10);
+
+        let lines: Vec<&str> = y_axis_string.lines().collect();
+        assert_eq!(lines.len(), 11);
+        assert_eq!(lines[0], "|-10  ");
+        assert_eq!(lines[4], "|-6  y");
+        assert_eq!(lines[10], "+-0   ");
+        assert!(lines
+            .iter()
+            .all(|l| l.starts_with('|') || l.starts_with('+')));
+        assert_eq!(width, 6);
+    }
+
     #[test]
     fn test_render_x_axis_strings() {
         let x_axis = axis::ContinuousAxis::new(0.0, 10.0, 6);
 
-        let (x_axis_string, start_offset) = render_x_axis_strings(&x_axis, 20);
+        let (x_axis_string, start_offset) = render_x_axis_strings(&[(&x_axis, 0, 20)], 20);
 
         assert!(x_axis_string.contains("0 "));
         assert!(x_axis_string.contains(" 6 "));
@@ -612,7 +1465,7 @@ mod tests {
         assert_eq!(strings.lines().count(), 10);
         assert!(strings.lines().all(|s| s.chars().count() == 20));
 
-        let comp = vec![
+        let comp = [
             "       ---          ",
             "       | |          ",
             "       | |          ",
@@ -629,6 +1482,98 @@ mod tests {
         assert_eq!(&strings, &comp);
     }
 
+    #[test]
+    fn test_render_categorical_x_axis_strings() {
+        let ticks = ["a".to_string(), "bb".to_string(), "cccccc".to_string()];
+        let x_axis = axis::CategoricalAxis::new(&ticks).label("X");
+        let strings = render_categorical_x_axis_strings(&x_axis, 12);
+        let comp = [
+            "+------------",
+            "  |   |   |  ",
+            "  a  bb  ccc ",
+            "     X      ",
+        ]
+        .join("\n");
+        assert_eq!(&strings, &comp);
+    }
+
+    #[test]
+    fn test_render_face_edge_markers() {
+        let data = [
+            (-1., 2.),
+            (5., 12.),
+            (5., -3.),
+            (11., 11.),
+            (3., 3.),
+            (f64::NAN, 1.),
+        ];
+        let x_axis = axis::ContinuousAxis::new(0., 10., 6);
+        let y_axis = axis::ContinuousAxis::new(0., 10., 6);
+        let strings = render_face_edge_markers(&data, &x_axis, &y_axis, 10, 5);
+        let comp = [
+            "    ▲    ◥",
+            "          ",
+            "          ",
+            "          ",
+            "◀   ▼     ",
+        ];
+        assert_eq!(strings, comp.join("\n"));
+    }
+
+    #[test]
+    fn test_render_face_grid() {
+        let lines = GridLines {
+            x: vec![0., 0.5, 1.],
+            y: vec![0., 0.5],
+            minor_x: vec![0.25],
+            minor_y: vec![0.75],
+        };
+        let strings = render_face_grid(&lines, 8, 4);
+        let comp = [" · ┆   ┆", "···┆···┆", "┄┄┄┼┄┄┄┼", " · ┆   ┆"];
+        assert_eq!(strings, comp.join("\n"));
+    }
+
+    #[test]
+    fn test_render_face_bar() {
+        let ticks = ["a".to_string(), "b".to_string()];
+        let x_axis = axis::CategoricalAxis::new(&ticks);
+        let y_axis = axis::ContinuousAxis::new(-2., 6., 6);
+        let strings = render_face_bar(4., "b", &x_axis, &y_axis, 12, 8);
+        assert_eq!(strings.lines().count(), 8);
+        assert!(strings.lines().all(|s| s.chars().count() == 12));
+        let comp = [
+            "            ",
+            "            ",
+            "       ---  ",
+            "       | |  ",
+            "       | |  ",
+            "       | |  ",
+            "       ---  ",
+            "            ",
+        ]
+        .join("\n");
+        assert_eq!(&strings, &comp);
+    }
+
+    #[test]
+    fn test_render_face_box() {
+        let ticks = ["a".to_string()];
+        let x_axis = axis::CategoricalAxis::new(&ticks);
+        let y_axis = axis::ContinuousAxis::new(0., 10., 6);
+        let data = [1., 2., 4., 5., 6., 8., 9.];
+        let strings = render_face_box(&data, "a", &x_axis, &y_axis, 8, 10);
+        let comp = [
+            "        ", "   |    ", "  ----  ", "  |  |  ", "  |  |  ", "  ====  ", "  |  |  ",
+            "  |  |  ", "  ----  ", "   |    ",
+        ]
+        .join("\n");
+        assert_eq!(&strings, &comp);
+        assert_eq!(
+            render_face_box(&[], "a", &x_axis, &y_axis, 8, 10),
+            render_face_box(&[], "b", &x_axis, &y_axis, 8, 10)
+        );
+    }
+
     #[test]
     fn test_render_face_points() {
         use crate::style::PointStyle;
@@ -648,7 +1593,7 @@ mod tests {
         assert_eq!(strings.lines().count(), 10);
         assert!(strings.lines().all(|s| s.chars().count() == 20));
 
-        let comp = vec![
+        let comp = [
             "  ●                 ",
             "                    ",
             "               ●    ",
@@ -665,6 +1610,126 @@ mod tests {
         assert_eq!(&strings, &comp);
     }
 
+    #[test]
+    fn test_line_glyph() {
+        assert_eq!(line_glyph(0.), '-');
+        assert_eq!(line_glyph(1.), '/');
+        assert_eq!(line_glyph(-1.), '\\');
+        assert_eq!(line_glyph(10.), '|');
+        assert_eq!(line_glyph(f64::INFINITY), '|');
+    }
+
+    #[test]
+    fn test_render_face_line() {
+        let x_axis = axis::ContinuousAxis::new(0., 10., 6);
+        let y_axis = axis::ContinuousAxis::new(0., 5., 6);
+        let data = vec![(1., 1.), (4., 4.), (7., 4.), (7., 2.), (20., 2.)];
+        let strings = render_face_line(&data, &x_axis, &y_axis, 10, 5);
+
+        // Later segments are drawn over the ends of earlier ones
+        let comp = [
+            "          ",
+            "   ---|   ",
+            "  /   |   ",
+            " /    ----",
+            "/         ",
+        ]
+        .join("\n");
+        assert_eq!(&strings, &comp);
+    }
+
+    #[test]
+    fn test_render_face_area() {
+        let x_axis = axis::ContinuousAxis::new(0., 10., 6);
+        let y_axis = axis::ContinuousAxis::new(0., 5., 6);
+        let strings = render_face_area(
+            &[(0., 2.), (10., 4.)],
+            &[(0., 0.), (10., 0.)],
+            &x_axis,
+            &y_axis,
+            10,
+            5,
+            '░',
+        );
+        let comp = [
+            "          ",
+            "       ░░░",
+            "  ░░░░░░░░",
+            "░░░░░░░░░░",
+            "░░░░░░░░░░",
+        ]
+        .join("\n");
+        assert_eq!(&strings, &comp);
+
+        // Only the columns which both edges cross are shaded
+        let strings = render_face_area(
+            &[(2., 4.), (6., 4.)],
+            &[(2., 2.), (5., 2.), (8., 2.)],
+            &x_axis,
+            &y_axis,
+            10,
+            5,
+            '░',
+        );
+        let comp = [
+            "          ",
+            " ░░░░░    ",
+            " ░░░░░    ",
+            " ░░░░░    ",
+            "          ",
+        ]
+        .join("\n");
+        assert_eq!(&strings, &comp);
+
+        // A layer sitting on another leaves the line of its lower edge to the one below
+        let strings = render_face_stacked_layer(
+            &[(2., 4.), (6., 4.)],
+            &[(2., 2.), (5., 2.), (8., 2.)],
+            &x_axis,
+            &y_axis,
+            10,
+            5,
+            '▒',
+        );
+        let comp = [
+            "          ",
+            " ▒▒▒▒▒    ",
+            " ▒▒▒▒▒    ",
+            "          ",
+            "          ",
+        ]
+        .join("\n");
+        assert_eq!(&strings, &comp);
+    }
+
+    #[test]
+    fn test_render_face_error_bars() {
+        let x_axis = axis::ContinuousAxis::new(0., 10., 6);
+        let y_axis = axis::ContinuousAxis::new(0., 5., 6);
+        let bars = vec![
+            ((1., 2.), (6., 2.)),
+            ((8., 5.), (20., 5.)),
+            ((3., 1.), (3., 4.)),
+        ];
+        let style = style::ErrorBarStyle::new();
+        let strings = render_face_error_bars(&bars, &x_axis, &y_axis, 10, 5, &style);
+
+        // The bar off the right of the face loses its cap there
+        let comp = [
+            "       ├──",
+            "  ┬       ",
+            "  │       ",
+            "├─┼──┤    ",
+            "  ┴       ",
+        ]
+        .join("\n");
+        assert_eq!(&strings, &comp);
+
+        let style = style::ErrorBarStyle::new().cap_width(0.);
+        let strings = render_face_error_bars(&bars[2..], &x_axis, &y_axis, 10, 5, &style);
+        assert_eq!(strings.lines().filter(|l| l.trim() == "│").count(), 4);
+    }
+
     #[test]
     fn test_overlay() {
         let a = " ooo ";
@@ -696,6 +1761,50 @@ mod tests {
         let b = "    \n  # \n #  \n    ";
         let r = "o#\n#o";
         assert_eq!(overlay(a, b, -1, -1), r);
+
+        let a = "·····\n·····";
+        let b = "●\n ●";
+        let r = "··●··\n···●·";
+        assert_eq!(overlay(a, b, 2, 0), r);
+
+        let a = "⠁⠂";
+        let b = "⠈⠈";
+        let r = "⠉⠊";
+        assert_eq!(overlay(a, b, 0, 0), r);
+    }
+
+    #[test]
+    fn test_coloured_overlay() {
+        let red = "\x1b[31m";
+        let a = format!("ab{}c\x1b[0m", red);
+        assert_eq!(display_width(&a), 3);
+        assert_eq!(overlay(&a, " x ", 0, 0), format!("ax{}c\x1b[0m", red));
+        assert_eq!(overlay("   ", &a, 1, 0), " ab");
+    }
+
+    #[test]
+    fn test_colourise() {
+        assert_eq!(colourise("o o", "red", Colours::None), "o o");
+        assert_eq!(colourise("o o", "black", Colours::TrueColour), "o o");
+        assert_eq!(colourise("o o", "nonsense", Colours::Ansi16), "o o");
+        assert_eq!(
+            colourise("o o", "red", Colours::TrueColour),
+            "\x1b[38;2;255;0;0mo\x1b[0m \x1b[38;2;255;0;0mo\x1b[0m"
+        );
+        assert_eq!(
+            colourise("o", "red", Colours::Ansi256),
+            "\x1b[38;5;196mo\x1b[0m"
+        );
+        assert_eq!(colourise("o", "red", Colours::Ansi16), "\x1b[31mo\x1b[0m");
+    }
+
+    #[test]
+    fn test_palettes() {
+        assert_eq!(ansi_256(Rgb(255, 255, 255)), 231);
+        assert_eq!(ansi_256(Rgb(128, 128, 128)), 244);
+        assert_eq!(ansi_256(Rgb(0, 0, 95)), 17);
+        assert_eq!(ansi_16(Rgb(0, 160, 0)), 32);
+        assert_eq!(ansi_16(Rgb(250, 250, 250)), 97);
     }
 
     #[test]
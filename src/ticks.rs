@@ -0,0 +1,286 @@
+#![deny(missing_docs)]
+
+//! Choose how the ticks of a continuous axis are placed.
+//!
+//! By default ticks are placed at the largest step of 1, 2, 4 or 5 times a power of ten
+//! which gives no more than the maximum number of ticks.
+//! The extended algorithm of Talbot, Lin and Hanrahan instead scores every candidate
+//! set of ticks on its simplicity, how well it covers the data, how close it comes to
+//! the wanted number of ticks and how legible it is, and picks the best.
+//! It can also widen the range of the axis so that it starts and ends on a tick.
+//!
+//! # Examples
+//!
+//! ```rust
+//! # use plotlib::repr::Plot;
+//! # use plotlib::view::ContinuousView;
+//! use plotlib::ticks::TickAlgorithm;
+//!
+//! let p = Plot::new(vec![(0.13, 3.2), (8.7, 27.)]);
+//! let v = ContinuousView::new()
+//!     .add(p)
+//!     .x_tick_algorithm(TickAlgorithm::Extended { expand: false })
+//!     .y_tick_algorithm(TickAlgorithm::Extended { expand: true });
+//! ```
+
+/// How the positions of the ticks along a linear axis are chosen
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum TickAlgorithm {
+    /// The largest step of 1, 2, 4 or 5 times a power of ten which fits the maximum number of ticks (the default)
+    #[default]
+    Steps,
+    /// The best scoring ticks of the extended Wilkinson algorithm, aiming for the maximum number of ticks.
+    /// If `expand` is set, the range of the axis is widened to the first and last tick,
+    /// otherwise all of the ticks lie within the range.
+    Extended {
+        /// Whether to widen the range of the axis to start and end on a tick
+        expand: bool,
+    },
+}
+
+/// The nice step sizes, most preferred first
+const Q: [f64; 6] = [1., 5., 2., 2.5, 4., 3.];
+
+/// The weights of simplicity, coverage, density and legibility
+const WEIGHTS: [f64; 4] = [0.25, 0.2, 0.5, 0.05];
+
+const EPSILON: f64 = 1e-10;
+
+/// A candidate set of ticks, running from `lower` to `upper` in steps of `step`
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Labelling {
+    lower: f64,
+    upper: f64,
+    step: f64,
+    /// The number of decimal places needed to write each tick exactly
+    places: usize,
+}
+
+impl Labelling {
+    fn ticks(&self) -> Vec<f64> {
+        let count = ((self.upper - self.lower) / self.step).round() as usize;
+        (0..=count)
+            .map(|i| {
+                let tick = self.lower + i as f64 * self.step;
+                // Remove floating-point errors, and avoid labelling zero as "-0"
+                let tick: f64 = format!("{:.*}", self.places, tick)
+                    .parse()
+                    .expect("ERROR: Could not parse tick value");
+                tick + 0.
+            })
+            .collect()
+    }
+}
+
+fn simplicity(q_index: usize, j: f64, lower: f64, upper: f64, step: f64) -> f64 {
+    let n = Q.len() as f64;
+    let remainder = lower.rem_euclid(step);
+    let has_zero =
+        (remainder < EPSILON || step - remainder < EPSILON) && lower <= 0. && upper >= 0.;
+    let v = if has_zero { 1. } else { 0. };
+    1. - q_index as f64 / (n - 1.) - j + v
+}
+
+fn simplicity_max(q_index: usize, j: f64) -> f64 {
+    let n = Q.len() as f64;
+    2. - q_index as f64 / (n - 1.) - j
+}
+
+fn coverage(dmin: f64, dmax: f64, lower: f64, upper: f64) -> f64 {
+    let range = dmax - dmin;
+    1. - 0.5 * ((dmax - upper).powi(2) + (dmin - lower).powi(2)) / (0.1 * range).powi(2)
+}
+
+fn coverage_max(dmin: f64, dmax: f64, span: f64) -> f64 {
+    let range = dmax - dmin;
+    if span > range {
+        let half = (span - range) / 2.;
+        1. - 0.5 * (2. * half.powi(2)) / (0.1 * range).powi(2)
+    } else {
+        1.
+    }
+}
+
+fn density(k: f64, m: f64, dmin: f64, dmax: f64, lower: f64, upper: f64) -> f64 {
+    let r = (k - 1.) / (upper - lower);
+    let rt = (m - 1.) / (upper.max(dmax) - dmin.min(lower));
+    2. - (r / rt).max(rt / r)
+}
+
+fn density_max(k: f64, m: f64) -> f64 {
+    if k >= m {
+        2. - (k - 1.) / (m - 1.)
+    } else {
+        1.
+    }
+}
+
+/// The largest power of ten tried for the step, beyond which it overflows
+const MAX_MAGNITUDE: i32 = f64::MAX_10_EXP;
+
+fn score(simplicity: f64, coverage: f64, density: f64, legibility: f64) -> f64 {
+    WEIGHTS[0] * simplicity + WEIGHTS[1] * coverage + WEIGHTS[2] * density + WEIGHTS[3] * legibility
+}
+
+/// Search for the best scoring labelling of the range `dmin` to `dmax` with at most `max_ticks` ticks
+///
+/// If `expand` is set the labelling must cover the range, otherwise it must lie within it.
+/// Returns `None` if no labelling is found, such as when the range is too wide to score.
+fn extended(dmin: f64, dmax: f64, max_ticks: usize, expand: bool) -> Option<Labelling> {
+    if dmax - dmin < EPSILON || max_ticks < 2 || !(dmin.is_finite() && dmax.is_finite()) {
+        return None;
+    }
+    // Avoid dividing by zero in the density when only two ticks are wanted
+    let m = (max_ticks as f64).max(2. + EPSILON);
+    let mut best_score = -2.;
+    let mut best = None;
+
+    let mut j = 1.;
+    'j: loop {
+        for (q_index, &q) in Q.iter().enumerate() {
+            let sm = simplicity_max(q_index, j);
+            if score(sm, 1., 1., 1.) < best_score {
+                break 'j;
+            }
+
+            for k in 2..=max_ticks {
+                let k = k as f64;
+                let dm = density_max(k, m);
+                if score(sm, 1., dm, 1.) < best_score {
+                    break;
+                }
+
+                let delta = (dmax - dmin) / (k + 1.) / j / q;
+                let mut z = delta.log10().ceil() as i32;
+                while z <= MAX_MAGNITUDE {
+                    let step = j * q * 10f64.powi(z);
+                    let cm = coverage_max(dmin, dmax, step * (k - 1.));
+                    let max_score = score(sm, cm, dm, 1.);
+                    // Huge ranges overflow the scores to NaN, which would never compare as worse
+                    if !step.is_finite() || !max_score.is_finite() || max_score < best_score {
+                        break;
+                    }
+
+                    let min_start = ((dmax / step).floor() * j - (k - 1.) * j) as i64;
+                    let max_start = ((dmin / step).ceil() * j) as i64;
+                    for start in min_start..=max_start {
+                        let lower = start as f64 * (step / j);
+                        let upper = lower + step * (k - 1.);
+                        let fits = if expand {
+                            lower <= dmin + EPSILON && upper >= dmax - EPSILON
+                        } else {
+                            lower >= dmin - EPSILON && upper <= dmax + EPSILON
+                        };
+                        if !fits {
+                            continue;
+                        }
+
+                        let s = simplicity(q_index, j, lower, upper, step);
+                        let c = coverage(dmin, dmax, lower, upper);
+                        let g = density(k, m, dmin, dmax, lower, upper);
+                        let this_score = score(s, c, g, 1.);
+                        if this_score.is_finite() && this_score > best_score {
+                            best_score = this_score;
+                            // Steps of 2.5 need one more decimal place than their power of ten
+                            let places = (-z).max(0) as usize + usize::from(q == 2.5);
+                            best = Some(Labelling {
+                                lower,
+                                upper,
+                                step,
+                                places,
+                            });
+                        }
+                    }
+                    z += 1;
+                }
+            }
+        }
+        j += 1.;
+    }
+
+    best
+}
+
+/// Ticks for the range `lower` to `upper` chosen by the extended algorithm,
+/// with the range widened to the first and last tick if `expand` is set
+///
+/// Returns `None` if no ticks could be found, such as for an empty range.
+pub(crate) fn calculate_extended_ticks(
+    lower: f64,
+    upper: f64,
+    max_ticks: usize,
+    expand: bool,
+) -> Option<(f64, f64, Vec<f64>)> {
+    let labelling = extended(lower, upper, max_ticks, expand)?;
+    let ticks = labelling.ticks();
+    if expand {
+        let first = *ticks.first()?;
+        let last = *ticks.last()?;
+        Some((lower.min(first), upper.max(last), ticks))
+    } else {
+        Some((lower, upper, ticks))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extended_ticks() {
+        let (lower, upper, ticks) = calculate_extended_ticks(0., 10., 6, false).unwrap();
+        assert_eq!((lower, upper), (0., 10.));
+        assert_eq!(ticks, [0., 2., 4., 6., 8., 10.]);
+
+        let (_, _, ticks) = calculate_extended_ticks(-0.13, 0.71, 6, false).unwrap();
+        assert_eq!(ticks, [0., 0.2, 0.4, 0.6]);
+
+        let (lower, upper, ticks) = calculate_extended_ticks(8.1, 14.2, 5, true).unwrap();
+        assert_eq!((lower, upper), (7.5, 15.));
+        assert_eq!(ticks, [7.5, 10., 12.5, 15.]);
+    }
+
+    #[test]
+    fn test_ticks_within_range() {
+        for &(lower, upper) in [(0.13, 0.17), (-7.93, 15.58), (1e6, 3.7e6), (5., 6.)].iter() {
+            for max_ticks in 2..10 {
+                let (_, _, ticks) = calculate_extended_ticks(lower, upper, max_ticks, false)
+                    .expect("some ticks are found");
+                assert!(!ticks.is_empty() && ticks.len() <= max_ticks);
+                assert!(ticks.iter().all(|&t| lower <= t && t <= upper));
+            }
+        }
+    }
+
+    #[test]
+    fn test_huge_range() {
+        // Gives up rather than searching forever
+        assert_eq!(calculate_extended_ticks(0., 1e300, 6, true), None);
+        assert_eq!(calculate_extended_ticks(0., 1e300, 6, false), None);
+        // so that the axis falls back to the plain linear ticks
+        let axis = crate::axis::ContinuousAxis::with_algorithm(
+            0.,
+            1e300,
+            6,
+            crate::scale::Scale::Linear,
+            TickAlgorithm::Extended { expand: true },
+        );
+        let linear = crate::axis::ContinuousAxis::new(0., 1e300, 6);
+        assert!(!axis.ticks().is_empty());
+        assert_eq!(axis.ticks(), linear.ticks());
+    }
+
+    #[test]
+    fn test_nan_score() {
+        // The width of this range overflows, so every score is NaN
+        assert!(!(f64::MAX - -f64::MAX).is_finite());
+        assert!(coverage(-f64::MAX, f64::MAX, 0., 1.).is_nan());
+        assert_eq!(calculate_extended_ticks(-f64::MAX, f64::MAX, 6, true), None);
+    }
+
+    #[test]
+    fn test_empty_range() {
+        assert_eq!(calculate_extended_ticks(1., 1., 6, true), None);
+        assert_eq!(calculate_extended_ticks(0., 1., 1, false), None);
+    }
+}
@@ -0,0 +1,330 @@
+#![deny(missing_docs)]
+
+//! Draw an axis of timestamps with calendar-aligned ticks.
+//!
+//! Times are given as seconds since the Unix epoch (1970-01-01 00:00:00 UTC),
+//! which is what `SystemTime::duration_since(UNIX_EPOCH)` and most time libraries produce.
+//! `timestamp` converts a calendar date and time in UTC to this form.
+//!
+//! Ticks are placed on whole seconds, minutes, hours, days, weeks (starting on Monday),
+//! months or years, whichever best fits the range of the axis.
+//! Their labels are written with a pattern in which the following fields are replaced:
+//!
+//! | Field | Meaning                     | Example   |
+//! |-------|-----------------------------|-----------|
+//! | `%Y`  | Year                        | `2020`    |
+//! | `%y`  | Year within the century     | `20`      |
+//! | `%m`  | Month                       | `03`      |
+//! | `%b`  | Abbreviated month name      | `Mar`     |
+//! | `%B`  | Month name                  | `March`   |
+//! | `%d`  | Day of the month            | `07`      |
+//! | `%e`  | Day of the month, unpadded  | `7`       |
+//! | `%a`  | Abbreviated weekday name    | `Sat`     |
+//! | `%j`  | Day of the year             | `067`     |
+//! | `%H`  | Hour                        | `09`      |
+//! | `%M`  | Minute                      | `05`      |
+//! | `%S`  | Second                      | `00`      |
+//! | `%%`  | A literal `%`               | `%`       |
+//!
+//! # Examples
+//!
+//! ```rust
+//! # use plotlib::repr::Plot;
+//! # use plotlib::view::ContinuousView;
+//! use plotlib::time::timestamp;
+//!
+//! let start = timestamp(2020, 3, 14, 0, 0, 0);
+//! let data: Vec<_> = (0..48).map(|h| (start + f64::from(h) * 3600., f64::from(h % 24))).collect();
+//! let v = ContinuousView::new()
+//!     .add(Plot::new(data))
+//!     .x_time()
+//!     .x_time_format("%d %b %H:%M");
+//! ```
+
+const MINUTE: i64 = 60;
+const HOUR: i64 = 60 * MINUTE;
+const DAY: i64 = 24 * HOUR;
+
+const MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+const WEEKDAYS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
+/// The number of days from 1970-01-01 to the given date in the proleptic Gregorian calendar
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = i64::from(month);
+    let day_of_year =
+        (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + i64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// The date which is the given number of days after 1970-01-01
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    } as u32;
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+/**
+The number of seconds since the Unix epoch of a date and time in UTC
+
+```rust
+# use plotlib::time::timestamp;
+assert_eq!(timestamp(1970, 1, 2, 0, 0, 0), 86400.);
+```
+*/
+pub fn timestamp(year: i32, month: u32, day: u32, hour: u32, minute: u32, second: u32) -> f64 {
+    let days = days_from_civil(i64::from(year), month, day);
+    (days * DAY + i64::from(hour) * HOUR + i64::from(minute) * MINUTE + i64::from(second)) as f64
+}
+
+/// Write the time `seconds` after the epoch using the fields in `pattern`
+pub(crate) fn format_time(seconds: f64, pattern: &str) -> String {
+    let seconds = seconds.round() as i64;
+    let days = seconds.div_euclid(DAY);
+    let time_of_day = seconds.rem_euclid(DAY);
+    let (year, month, day) = civil_from_days(days);
+    let month_name = MONTHS[month as usize - 1];
+
+    let mut formatted = String::new();
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            formatted.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('Y') => formatted.push_str(&year.to_string()),
+            Some('y') => formatted.push_str(&format!("{:02}", year.rem_euclid(100))),
+            Some('m') => formatted.push_str(&format!("{:02}", month)),
+            Some('b') => formatted.push_str(&month_name[..3]),
+            Some('B') => formatted.push_str(month_name),
+            Some('d') => formatted.push_str(&format!("{:02}", day)),
+            Some('e') => formatted.push_str(&day.to_string()),
+            Some('a') => formatted.push_str(WEEKDAYS[(days + 3).rem_euclid(7) as usize]),
+            Some('j') => {
+                formatted.push_str(&format!("{:03}", days - days_from_civil(year, 1, 1) + 1))
+            }
+            Some('H') => formatted.push_str(&format!("{:02}", time_of_day / HOUR)),
+            Some('M') => formatted.push_str(&format!("{:02}", time_of_day % HOUR / MINUTE)),
+            Some('S') => formatted.push_str(&format!("{:02}", time_of_day % MINUTE)),
+            Some('%') => formatted.push('%'),
+            Some(other) => {
+                formatted.push('%');
+                formatted.push(other);
+            }
+            None => formatted.push('%'),
+        }
+    }
+    formatted
+}
+
+/// The calendar intervals which ticks may be spaced by
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Step {
+    Seconds(i64),
+    Weeks(i64),
+    Months(i64),
+    Years(i64),
+}
+
+const STEPS: [Step; 27] = [
+    Step::Seconds(1),
+    Step::Seconds(2),
+    Step::Seconds(5),
+    Step::Seconds(10),
+    Step::Seconds(15),
+    Step::Seconds(30),
+    Step::Seconds(MINUTE),
+    Step::Seconds(2 * MINUTE),
+    Step::Seconds(5 * MINUTE),
+    Step::Seconds(10 * MINUTE),
+    Step::Seconds(15 * MINUTE),
+    Step::Seconds(30 * MINUTE),
+    Step::Seconds(HOUR),
+    Step::Seconds(2 * HOUR),
+    Step::Seconds(3 * HOUR),
+    Step::Seconds(6 * HOUR),
+    Step::Seconds(12 * HOUR),
+    Step::Seconds(DAY),
+    Step::Seconds(2 * DAY),
+    Step::Weeks(1),
+    Step::Weeks(2),
+    Step::Months(1),
+    Step::Months(2),
+    Step::Months(3),
+    Step::Months(6),
+    Step::Years(1),
+    Step::Years(2),
+];
+
+impl Step {
+    /// The pattern used for labels when none is given
+    fn default_pattern(self) -> &'static str {
+        match self {
+            Step::Seconds(s) if s < MINUTE => "%H:%M:%S",
+            Step::Seconds(s) if s < DAY => "%H:%M",
+            Step::Seconds(_) | Step::Weeks(_) => "%Y-%m-%d",
+            Step::Months(_) => "%b %Y",
+            Step::Years(_) => "%Y",
+        }
+    }
+
+    /// The typical length of the step, for ruling out ones which are much too short
+    fn approximate_seconds(self) -> f64 {
+        match self {
+            Step::Seconds(step) => step as f64,
+            Step::Weeks(step) => (step * 7 * DAY) as f64,
+            Step::Months(step) => step as f64 * 30.44 * DAY as f64,
+            Step::Years(step) => step as f64 * 365.25 * DAY as f64,
+        }
+    }
+
+    /// The ticks between `lower` and `upper` which fall on a whole number of steps
+    fn ticks(self, lower: f64, upper: f64) -> Vec<f64> {
+        match self {
+            Step::Seconds(step) | Step::Weeks(step) => {
+                let (step, offset) = match self {
+                    // 1970-01-01 was a Thursday, so weeks start 4 days later
+                    Step::Weeks(_) => (step * 7 * DAY, 4 * DAY),
+                    _ => (step, 0),
+                };
+                let first = ((lower - offset as f64) / step as f64).ceil() as i64;
+                (first..)
+                    .map(|n| (n * step + offset) as f64)
+                    .take_while(|&t| t <= upper)
+                    .collect()
+            }
+            Step::Months(step) | Step::Years(step) => {
+                let step = match self {
+                    Step::Years(_) => step * 12,
+                    _ => step,
+                };
+                let (year, month, _) = civil_from_days((lower / DAY as f64).floor() as i64);
+                let first_month = year * 12 + i64::from(month) - 1;
+                let first = first_month.div_euclid(step) * step;
+                (0..)
+                    .map(|n| {
+                        let months = first + n * step;
+                        let days = days_from_civil(
+                            months.div_euclid(12),
+                            months.rem_euclid(12) as u32 + 1,
+                            1,
+                        );
+                        (days * DAY) as f64
+                    })
+                    .skip_while(|&t| t < lower)
+                    .take_while(|&t| t <= upper)
+                    .collect()
+            }
+        }
+    }
+}
+
+/// Given a range of timestamps, choose the calendar-aligned ticks
+/// and the pattern their labels should be written with by default
+pub(crate) fn calculate_time_ticks(lower: f64, upper: f64, max_ticks: usize) -> (Vec<f64>, String) {
+    let max_ticks = max_ticks.max(2);
+    // Beyond a couple of years, ticks go on the years which are multiples of a round number
+    let long_steps = (0..10).flat_map(|power| {
+        [5, 10, 20]
+            .iter()
+            .map(move |&years| Step::Years(years * 10_i64.pow(power)))
+    });
+    let mut ticks = vec![];
+    let mut pattern = "%Y";
+    for step in STEPS.iter().cloned().chain(long_steps) {
+        if (upper - lower) / step.approximate_seconds() > (max_ticks + 1) as f64 {
+            continue;
+        }
+        ticks = step.ticks(lower, upper);
+        pattern = step.default_pattern();
+        if ticks.len() <= max_ticks {
+            break;
+        }
+    }
+    (ticks, pattern.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_civil_days() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(days_from_civil(2000, 3, 1), 11017);
+        assert_eq!(days_from_civil(1969, 12, 31), -1);
+        for &days in [-800_000, -1, 0, 59, 11016, 11017, 18_335, 2_000_000].iter() {
+            let (y, m, d) = civil_from_days(days);
+            assert_eq!(days_from_civil(y, m, d), days);
+        }
+        assert_eq!(civil_from_days(18_335), (2020, 3, 14));
+    }
+
+    #[test]
+    fn test_format_time() {
+        let t = timestamp(2020, 3, 7, 9, 5, 42);
+        assert_eq!(format_time(t, "%Y-%m-%d %H:%M:%S"), "2020-03-07 09:05:42");
+        assert_eq!(
+            format_time(t, "%a %e %B %y, day %j"),
+            "Sat 7 March 20, day 067"
+        );
+        assert_eq!(format_time(t, "100%% %b %q"), "100% Mar %q");
+        assert_eq!(format_time(-1., "%Y-%m-%d %H:%M:%S"), "1969-12-31 23:59:59");
+    }
+
+    #[test]
+    fn test_time_ticks() {
+        let start = timestamp(2020, 3, 14, 10, 17, 0);
+        let (ticks, pattern) = calculate_time_ticks(start, start + 5. * 3600., 6);
+        assert_eq!(pattern, "%H:%M");
+        assert_eq!(ticks[0], timestamp(2020, 3, 14, 11, 0, 0));
+        assert_eq!(ticks.len(), 5);
+
+        let (ticks, pattern) = calculate_time_ticks(start, start + 40. * 86400., 6);
+        assert_eq!(pattern, "%Y-%m-%d");
+        // Weeks start on a Monday
+        assert_eq!(format_time(ticks[0], "%a %d"), "Mon 16");
+
+        let (ticks, pattern) = calculate_time_ticks(start, timestamp(2021, 1, 1, 0, 0, 0), 6);
+        assert_eq!(pattern, "%b %Y");
+        let labels: Vec<_> = ticks.iter().map(|&t| format_time(t, &pattern)).collect();
+        assert_eq!(
+            labels,
+            ["May 2020", "Jul 2020", "Sep 2020", "Nov 2020", "Jan 2021"]
+        );
+
+        let (ticks, pattern) = calculate_time_ticks(start, timestamp(2061, 1, 1, 0, 0, 0), 6);
+        assert_eq!(pattern, "%Y");
+        let labels: Vec<_> = ticks.iter().map(|&t| format_time(t, &pattern)).collect();
+        assert_eq!(labels, ["2030", "2040", "2050", "2060"]);
+    }
+}
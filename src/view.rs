@@ -14,32 +14,93 @@ use svg::Node;
 
 use crate::axis;
 use crate::errors::Result;
-use crate::grid::{Grid, GridType};
+use crate::format::TickFormat;
+use crate::grid::Grid;
+use crate::range::RangePolicy;
 use crate::repr::{CategoricalRepresentation, ContinuousRepresentation};
+use crate::scale::Scale;
 use crate::svg_render;
+use crate::terminal::Colours;
 use crate::text_render;
+use crate::ticks::TickAlgorithm;
 use crate::utils;
+use crate::utils::PairWise;
 
 pub trait View {
     fn to_svg(&self, face_width: f64, face_height: f64) -> Result<svg::node::element::Group>;
     fn to_text(&self, face_width: u32, face_height: u32) -> Result<String>;
+    /// Like `to_text` but with the face drawn in braille patterns where supported
+    fn to_braille(&self, face_width: u32, face_height: u32) -> Result<String> {
+        self.to_text(face_width, face_height)
+    }
+    /// Like `to_text`, or `to_braille` if `braille` is set,
+    /// but with each representation drawn in its colour where supported
+    fn to_coloured_text(
+        &self,
+        face_width: u32,
+        face_height: u32,
+        braille: bool,
+        _colours: Colours,
+    ) -> Result<String> {
+        if braille {
+            self.to_braille(face_width, face_height)
+        } else {
+            self.to_text(face_width, face_height)
+        }
+    }
     fn add_grid(&mut self, grid: Grid);
     fn grid(&self) -> &Option<Grid>;
+    /// Whether the view draws an axis on the right of its face, which needs room beside it
+    fn has_secondary_axis(&self) -> bool {
+        false
+    }
 }
 
 /// Standard 1-dimensional view with a continuous x-axis
-#[derive(Default)]
 pub struct ContinuousView {
     representations: Vec<Box<dyn ContinuousRepresentation>>,
     x_range: Option<axis::Range>,
     y_range: Option<axis::Range>,
+    x_range_policy: RangePolicy,
+    y_range_policy: RangePolicy,
+    x_segments: Option<Vec<axis::Range>>,
+    y_segments: Option<Vec<axis::Range>>,
     x_max_ticks: usize,
     y_max_ticks: usize,
+    x_scale: Scale,
+    y_scale: Scale,
+    x_reversed: bool,
+    y_reversed: bool,
+    x_tick_algorithm: TickAlgorithm,
+    y_tick_algorithm: TickAlgorithm,
+    x_time: bool,
+    x_tick_format: Option<TickFormat>,
+    y_tick_format: Option<TickFormat>,
+    x_ticks: Option<Vec<(f64, Option<String>)>>,
+    y_ticks: Option<Vec<(f64, Option<String>)>>,
+    x_minor_ticks: Option<u32>,
+    y_minor_ticks: Option<u32>,
     x_label: Option<String>,
     y_label: Option<String>,
+    secondary_representations: Vec<Box<dyn ContinuousRepresentation>>,
+    y2_range: Option<axis::Range>,
+    y2_range_policy: RangePolicy,
+    y2_max_ticks: usize,
+    y2_scale: Scale,
+    y2_reversed: bool,
+    y2_tick_format: Option<TickFormat>,
+    y2_ticks: Option<Vec<(f64, Option<String>)>>,
+    y2_label: Option<String>,
+    edge_markers: bool,
     grid: Option<Grid>,
 }
 
+impl Default for ContinuousView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl ContinuousView {
     /// Create an empty view
     pub fn new() -> ContinuousView {
@@ -47,10 +108,37 @@ impl ContinuousView {
             representations: vec![],
             x_range: None,
             y_range: None,
+            x_range_policy: RangePolicy::exact(),
+            y_range_policy: RangePolicy::exact(),
+            x_segments: None,
+            y_segments: None,
             x_max_ticks: 6,
             y_max_ticks: 6,
+            x_scale: Scale::Linear,
+            y_scale: Scale::Linear,
+            x_reversed: false,
+            y_reversed: false,
+            x_tick_algorithm: TickAlgorithm::Steps,
+            y_tick_algorithm: TickAlgorithm::Steps,
+            x_time: false,
+            x_tick_format: None,
+            y_tick_format: None,
+            x_ticks: None,
+            y_ticks: None,
+            x_minor_ticks: None,
+            y_minor_ticks: None,
             x_label: None,
             y_label: None,
+            secondary_representations: vec![],
+            y2_range: None,
+            y2_range_policy: RangePolicy::exact(),
+            y2_max_ticks: 6,
+            y2_scale: Scale::Linear,
+            y2_reversed: false,
+            y2_tick_format: None,
+            y2_ticks: None,
+            y2_label: None,
+            edge_markers: false,
             grid: None,
         }
     }
@@ -65,7 +153,121 @@ impl ContinuousView {
         self
     }
 
+    /// Set the scale of the x axis.
+    pub fn x_scale(mut self, scale: Scale) -> Self {
+        self.x_scale = scale;
+        self
+    }
+    /// Set the scale of the y axis.
+    pub fn y_scale(mut self, scale: Scale) -> Self {
+        self.y_scale = scale;
+        self
+    }
+
+    /// Reverse the x axis, so that it grows leftward.
+    pub fn x_reverse(mut self) -> Self {
+        self.x_reversed = true;
+        self
+    }
+    /// Reverse the y axis, so that it grows downward.
+    pub fn y_reverse(mut self) -> Self {
+        self.y_reversed = true;
+        self
+    }
+
+    /// Set how the ticks along the x axis are chosen.
+    pub fn x_tick_algorithm(mut self, algorithm: TickAlgorithm) -> Self {
+        self.x_tick_algorithm = algorithm;
+        self
+    }
+    /// Set how the ticks along the y axis are chosen.
+    pub fn y_tick_algorithm(mut self, algorithm: TickAlgorithm) -> Self {
+        self.y_tick_algorithm = algorithm;
+        self
+    }
+
+    /// Treat x values as timestamps, in seconds since the Unix epoch,
+    /// and tick the x axis at whole minutes, hours, days, months or years.
+    /// The axis is always linear, whatever `x_scale` is set to.
+    /// See the `time` module.
+    pub fn x_time(mut self) -> Self {
+        self.x_time = true;
+        self
+    }
+    /// Treat x values as timestamps and label the ticks with a pattern like `"%Y-%m-%d"`.
+    /// See the `time` module for the fields which may be used.
+    pub fn x_time_format<T>(mut self, pattern: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.x_time = true;
+        self.x_tick_format = Some(TickFormat::Time(pattern.into()));
+        self
+    }
+    /// Set how the x axis ticks are labelled.
+    pub fn x_tick_format(mut self, format: TickFormat) -> Self {
+        self.x_tick_format = Some(format);
+        self
+    }
+    /// Set how the y axis ticks are labelled.
+    pub fn y_tick_format(mut self, format: TickFormat) -> Self {
+        self.y_tick_format = Some(format);
+        self
+    }
+
+    /// Place the x axis ticks at exactly these values, instead of choosing them automatically.
+    pub fn x_ticks(mut self, ticks: &[f64]) -> Self {
+        self.x_ticks = Some(ticks.iter().map(|&t| (t, None)).collect());
+        self
+    }
+    /// Place the y axis ticks at exactly these values, instead of choosing them automatically.
+    pub fn y_ticks(mut self, ticks: &[f64]) -> Self {
+        self.y_ticks = Some(ticks.iter().map(|&t| (t, None)).collect());
+        self
+    }
+    /// Place the x axis ticks at exactly these values, each labelled with its string.
+    pub fn x_tick_labels<T>(mut self, ticks: &[(f64, T)]) -> Self
+    where
+        T: AsRef<str>,
+    {
+        self.x_ticks = Some(
+            ticks
+                .iter()
+                .map(|(t, l)| (*t, Some(l.as_ref().to_string())))
+                .collect(),
+        );
+        self
+    }
+    /// Place the y axis ticks at exactly these values, each labelled with its string.
+    pub fn y_tick_labels<T>(mut self, ticks: &[(f64, T)]) -> Self
+    where
+        T: AsRef<str>,
+    {
+        self.y_ticks = Some(
+            ticks
+                .iter()
+                .map(|(t, l)| (*t, Some(l.as_ref().to_string())))
+                .collect(),
+        );
+        self
+    }
+
+    /// Draw minor ticks on the x axis which divide the gaps between major ticks into `parts`.
+    /// Logarithmic scales have minor ticks at each multiple within a decade by default,
+    /// and any scale can have them turned off by passing 0.
+    pub fn x_minor_ticks(mut self, parts: u32) -> Self {
+        self.x_minor_ticks = Some(parts);
+        self
+    }
+    /// Draw minor ticks on the y axis which divide the gaps between major ticks into `parts`.
+    /// See `x_minor_ticks`.
+    pub fn y_minor_ticks(mut self, parts: u32) -> Self {
+        self.y_minor_ticks = Some(parts);
+        self
+    }
+
     /// Add a representation to the view
+    #[allow(clippy::should_implement_trait)]
     pub fn add<R: ContinuousRepresentation + 'static>(mut self, repr: R) -> Self {
         self.representations.push(Box::new(repr));
         self
@@ -83,6 +285,44 @@ impl ContinuousView {
         self
     }
 
+    /// Set how the x range is fitted to the data when it is not set explicitly.
+    pub fn x_range_policy(mut self, policy: RangePolicy) -> Self {
+        self.x_range_policy = policy;
+        self
+    }
+
+    /// Set how the y range is fitted to the data when it is not set explicitly.
+    pub fn y_range_policy(mut self, policy: RangePolicy) -> Self {
+        self.y_range_policy = policy;
+        self
+    }
+
+    /// Break the x axis into segments covering each of the given ranges, in increasing order,
+    /// with a break marker between them. This takes the place of the x range.
+    /// Edge markers are not drawn on a view with a broken axis.
+    pub fn x_segments(mut self, ranges: &[(f64, f64)]) -> Self {
+        self.x_segments = Some(
+            ranges
+                .iter()
+                .map(|&(lower, upper)| axis::Range::new(lower, upper))
+                .collect(),
+        );
+        self
+    }
+
+    /// Break the y axis into segments covering each of the given ranges, in increasing order,
+    /// with a break marker between them. This takes the place of the y range.
+    /// Edge markers are not drawn on a view with a broken axis.
+    pub fn y_segments(mut self, ranges: &[(f64, f64)]) -> Self {
+        self.y_segments = Some(
+            ranges
+                .iter()
+                .map(|&(lower, upper)| axis::Range::new(lower, upper))
+                .collect(),
+        );
+        self
+    }
+
     /// Set the label for the x-axis
     pub fn x_label<T>(mut self, value: T) -> Self
     where
@@ -101,15 +341,232 @@ impl ContinuousView {
         self
     }
 
+    /// Add a representation to the view, measured against the secondary y axis on the right
+    pub fn add_secondary<R: ContinuousRepresentation + 'static>(mut self, repr: R) -> Self {
+        self.secondary_representations.push(Box::new(repr));
+        self
+    }
+
+    /// Set the range of the secondary y axis, drawing it even if nothing is added to it
+    pub fn y2_range(mut self, min: f64, max: f64) -> Self {
+        self.y2_range = Some(axis::Range::new(min, max));
+        self
+    }
+
+    /// Set how the secondary y range is fitted to the data when it is not set explicitly.
+    pub fn y2_range_policy(mut self, policy: RangePolicy) -> Self {
+        self.y2_range_policy = policy;
+        self
+    }
+
+    /// Set the maximum number of ticks along the secondary y axis.
+    pub fn y2_max_ticks(mut self, val: usize) -> Self {
+        self.y2_max_ticks = val;
+        self
+    }
+
+    /// Set the scale of the secondary y axis.
+    pub fn y2_scale(mut self, scale: Scale) -> Self {
+        self.y2_scale = scale;
+        self
+    }
+
+    /// Set how the secondary y axis ticks are labelled.
+    pub fn y2_tick_format(mut self, format: TickFormat) -> Self {
+        self.y2_tick_format = Some(format);
+        self
+    }
+
+    /// Place the secondary y axis ticks at exactly these values, instead of choosing them automatically.
+    pub fn y2_ticks(mut self, ticks: &[f64]) -> Self {
+        self.y2_ticks = Some(ticks.iter().map(|&t| (t, None)).collect());
+        self
+    }
+
+    /// Set the label for the secondary y-axis
+    pub fn y2_label<T>(mut self, value: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.y2_label = Some(value.into());
+        self
+    }
+
+    /// Reverse the secondary y-axis, so that it grows downward
+    pub fn y2_reverse(mut self) -> Self {
+        self.y2_reversed = true;
+        self
+    }
+
+    /// Mark the edge of the face with an arrow for each point which lies outside the ranges,
+    /// rather than leaving them hidden.
+    pub fn edge_markers(mut self) -> Self {
+        self.edge_markers = true;
+        self
+    }
+
+    /// Draw the axes around the face strings made for each representation
+    fn render_text<F>(
+        &self,
+        face_width: u32,
+        face_height: u32,
+        colours: Colours,
+        render_face: F,
+    ) -> Result<String>
+    where
+        F: Fn(
+            &dyn ContinuousRepresentation,
+            &axis::ContinuousAxis,
+            &axis::ContinuousAxis,
+            u32,
+            u32,
+        ) -> Vec<(String, String)>,
+    {
+        let (x_axes, y_axes) = self.create_axes()?;
+        let y2_axis = self.create_secondary_axis()?;
+        if y2_axis.is_some() && y_axes.len() > 1 {
+            return Err(format_err!(
+                "A secondary y-axis cannot be drawn beside a broken y-axis"
+            ));
+        }
+        let x_segments = cell_segments(&x_axes, face_width);
+        let y_segments = cell_segments(&y_axes, face_height);
+
+        let (y_axis_string, longest_y_label_width) =
+            text_render::render_y_axis_strings(&y_segments, face_height);
+        let (y2_axis_string, y2_axis_width) = match &y2_axis {
+            Some(y2_axis) => text_render::render_secondary_y_axis_strings(y2_axis, face_height),
+            None => (String::new(), 0),
+        };
+
+        let (x_axis_string, start_offset) =
+            text_render::render_x_axis_strings(&x_segments, face_width);
+
+        let left_gutter_width =
+            std::cmp::max(longest_y_label_width + 3, start_offset.wrapping_neg()) as u32;
+
+        // Leave room for the last x-axis label to hang off the right of the axis
+        let x_axis_width = x_axis_string
+            .lines()
+            .map(text_render::display_width)
+            .max()
+            .unwrap_or(0) as i32
+            + left_gutter_width as i32
+            + start_offset.min(0);
+        let view_width = std::cmp::max(
+            face_width + 1 + left_gutter_width + 1 + y2_axis_width as u32,
+            x_axis_width as u32,
+        );
+        let view_height = face_height + 4;
+
+        let blank: Vec<String> = (0..view_height)
+            .map(|_| (0..view_width).map(|_| ' ').collect())
+            .collect();
+        let mut view_string = blank.join("\n");
+
+        // Draw the face once for each pair of segments of the axes, from its top-left cell
+        for &(x_axis, x_offset, width) in &x_segments {
+            for &(y_axis, y_offset, height) in &y_segments {
+                let column = (left_gutter_width + 1 + x_offset) as i32;
+                let line = (face_height - y_offset - height) as i32;
+
+                // The grid is drawn first so that it sits under the data
+                if let Some(grid) = &self.grid {
+                    let grid_string = text_render::render_face_grid(
+                        &grid.continuous(x_axis, y_axis),
+                        width,
+                        height,
+                    );
+                    view_string = text_render::overlay(
+                        &view_string,
+                        &text_render::colourise(&grid_string, &grid.color, colours),
+                        column,
+                        line,
+                    );
+                }
+
+                for &(repr, y_axis) in &self.layers(y_axis, y2_axis.as_ref()) {
+                    for (layer, colour) in render_face(repr, x_axis, y_axis, width, height) {
+                        let face_string = text_render::colourise(&layer, &colour, colours);
+                        view_string =
+                            text_render::overlay(&view_string, &face_string, column, line);
+                    }
+                }
+            }
+        }
+
+        if self.edge_markers && x_axes.len() == 1 && y_axes.len() == 1 {
+            for &(repr, y_axis) in &self.layers(&y_axes[0], y2_axis.as_ref()) {
+                let markers = text_render::render_face_edge_markers(
+                    repr.points(),
+                    &x_axes[0],
+                    y_axis,
+                    face_width,
+                    face_height,
+                );
+                view_string =
+                    text_render::overlay(&view_string, &markers, left_gutter_width as i32 + 1, 0);
+            }
+        }
+
+        let view_string = text_render::overlay(
+            &view_string,
+            &y_axis_string,
+            left_gutter_width as i32 - 2 - longest_y_label_width,
+            0,
+        );
+        let view_string = text_render::overlay(
+            &view_string,
+            &x_axis_string,
+            // Labels hanging off the left of the axis are included in the string
+            left_gutter_width as i32 + start_offset.min(0),
+            face_height as i32,
+        );
+        let view_string = text_render::overlay(
+            &view_string,
+            &y2_axis_string,
+            (left_gutter_width + face_width) as i32 + 1,
+            0,
+        );
+
+        Ok(view_string)
+    }
+
+    /// Each representation with the y-axis it is measured against
+    fn layers<'a>(
+        &'a self,
+        y_axis: &'a axis::ContinuousAxis,
+        y2_axis: Option<&'a axis::ContinuousAxis>,
+    ) -> Vec<(&'a dyn ContinuousRepresentation, &'a axis::ContinuousAxis)> {
+        let primary = self.representations.iter().map(|r| (r.as_ref(), y_axis));
+        let secondary = y2_axis.into_iter().flat_map(|y2_axis| {
+            self.secondary_representations
+                .iter()
+                .map(move |r| (r.as_ref(), y2_axis))
+        });
+        primary.chain(secondary).collect()
+    }
+
     fn default_x_range(&self) -> axis::Range {
         let mut x_min = f64::INFINITY;
         let mut x_max = f64::NEG_INFINITY;
-        for repr in &self.representations {
+        let representations = self
+            .representations
+            .iter()
+            .chain(&self.secondary_representations);
+        for repr in representations {
             let (this_x_min, this_x_max) = repr.range(0);
             x_min = x_min.min(this_x_min);
             x_max = x_max.max(this_x_max);
         }
         let (x_min, x_max) = utils::pad_range_to_zero(x_min, x_max);
+        let (x_min, x_max) = self.x_range_policy.apply(
+            x_min,
+            x_max,
+            self.x_scale,
+            self.x_max_ticks,
+            self.x_tick_algorithm,
+        );
         axis::Range::new(x_min, x_max)
     }
 
@@ -122,39 +579,215 @@ impl ContinuousView {
             y_max = y_max.max(this_y_max);
         }
         let (y_min, y_max) = utils::pad_range_to_zero(y_min, y_max);
+        let (y_min, y_max) = self.y_range_policy.apply(
+            y_min,
+            y_max,
+            self.y_scale,
+            self.y_max_ticks,
+            self.y_tick_algorithm,
+        );
         axis::Range::new(y_min, y_max)
     }
 
-    fn create_axes(&self) -> Result<(axis::ContinuousAxis, axis::ContinuousAxis)> {
-        let default_x_range = self.default_x_range();
-        let x_range = self.x_range.as_ref().unwrap_or(&default_x_range);
-        if !x_range.is_valid() {
-            return Err(format_err!(
-                "Invalid x_range: {} >= {}. Please specify the x_range manually.",
+    /// Create the axes, with one for each segment of a broken axis
+    fn create_axes(&self) -> Result<(Vec<axis::ContinuousAxis>, Vec<axis::ContinuousAxis>)> {
+        let x_ranges = match &self.x_segments {
+            Some(segments) => {
+                check_segments("x_segments", segments, self.x_scale)?;
+                segments.clone()
+            }
+            None => {
+                let default_x_range = self.default_x_range();
+                let x_range = self.x_range.as_ref().unwrap_or(&default_x_range);
+                if !x_range.is_valid() {
+                    return Err(format_err!(
+                        "Invalid x_range: {} >= {}. Please specify the x_range manually.",
+                        x_range.lower,
+                        x_range.upper
+                    ));
+                }
+                if !self.x_scale.is_valid_range(x_range.lower, x_range.upper) {
+                    return Err(format_err!(
+                        "Invalid x_range: {} to {} cannot be drawn with {:?} scale. Please specify the x_range manually.",
+                        x_range.lower,
+                        x_range.upper,
+                        self.x_scale
+                    ));
+                }
+                vec![x_range.clone()]
+            }
+        };
+
+        let y_ranges = match &self.y_segments {
+            Some(segments) => {
+                check_segments("y_segments", segments, self.y_scale)?;
+                segments.clone()
+            }
+            None => {
+                let default_y_range = self.default_y_range();
+                let y_range = self.y_range.as_ref().unwrap_or(&default_y_range);
+                if !y_range.is_valid() {
+                    return Err(format_err!(
+                        "Invalid y_range: {} >= {}. Please specify the y_range manually.",
+                        y_range.lower,
+                        y_range.upper
+                    ));
+                }
+                if !self.y_scale.is_valid_range(y_range.lower, y_range.upper) {
+                    return Err(format_err!(
+                        "Invalid y_range: {} to {} cannot be drawn with {:?} scale. Please specify the y_range manually.",
+                        y_range.lower,
+                        y_range.upper,
+                        self.y_scale
+                    ));
+                }
+                vec![y_range.clone()]
+            }
+        };
+
+        let x_ticks = segment_max_ticks(&x_ranges, self.x_scale, self.x_max_ticks);
+        let y_ticks = segment_max_ticks(&y_ranges, self.y_scale, self.y_max_ticks);
+        let x_axes = x_ranges
+            .iter()
+            .zip(x_ticks)
+            .map(|(r, max_ticks)| self.create_x_axis(r, max_ticks))
+            .collect();
+        let y_axes = y_ranges
+            .iter()
+            .zip(y_ticks)
+            .map(|(r, max_ticks)| self.create_y_axis(r, max_ticks))
+            .collect();
+        Ok((x_axes, y_axes))
+    }
+
+    fn create_x_axis(&self, x_range: &axis::Range, max_ticks: usize) -> axis::ContinuousAxis {
+        let x_label: String = self.x_label.clone().unwrap_or_default();
+        let x_axis = if self.x_time {
+            axis::ContinuousAxis::time(x_range.lower, x_range.upper, max_ticks)
+        } else {
+            axis::ContinuousAxis::with_algorithm(
                 x_range.lower,
-                x_range.upper
-            ));
+                x_range.upper,
+                max_ticks,
+                self.x_scale,
+                self.x_tick_algorithm,
+            )
         }
+        .label(x_label);
 
-        let default_y_range = self.default_y_range();
-        let y_range = self.y_range.as_ref().unwrap_or(&default_y_range);
-        if !y_range.is_valid() {
-            return Err(format_err!(
-                "Invalid y_range: {} >= {}. Please specify the y_range manually.",
-                y_range.lower,
-                y_range.upper
-            ));
+        let x_axis = match &self.x_tick_format {
+            Some(format) => x_axis.format(format.clone()),
+            None => x_axis,
+        };
+        let x_axis = match &self.x_ticks {
+            Some(ticks) => x_axis.explicit_ticks(ticks),
+            None => x_axis,
+        };
+        let x_axis = match self.x_minor_ticks {
+            Some(parts) => x_axis.subdivide(parts),
+            None => x_axis,
+        };
+        if self.x_reversed {
+            x_axis.reverse()
+        } else {
+            x_axis
         }
+    }
 
-        let x_label: String = self.x_label.clone().unwrap_or_else(|| "".to_string());
-        let y_label: String = self.y_label.clone().unwrap_or_else(|| "".to_string());
+    fn create_y_axis(&self, y_range: &axis::Range, max_ticks: usize) -> axis::ContinuousAxis {
+        let y_label: String = self.y_label.clone().unwrap_or_default();
+        let y_axis = axis::ContinuousAxis::with_algorithm(
+            y_range.lower,
+            y_range.upper,
+            max_ticks,
+            self.y_scale,
+            self.y_tick_algorithm,
+        )
+        .label(y_label);
 
-        let x_axis = axis::ContinuousAxis::new(x_range.lower, x_range.upper, self.x_max_ticks)
-            .label(x_label);
-        let y_axis = axis::ContinuousAxis::new(y_range.lower, y_range.upper, self.y_max_ticks)
-            .label(y_label);
+        let y_axis = match &self.y_tick_format {
+            Some(format) => y_axis.format(format.clone()),
+            None => y_axis,
+        };
+        let y_axis = match &self.y_ticks {
+            Some(ticks) => y_axis.explicit_ticks(ticks),
+            None => y_axis,
+        };
+        let y_axis = match self.y_minor_ticks {
+            Some(parts) => y_axis.subdivide(parts),
+            None => y_axis,
+        };
+        if self.y_reversed {
+            y_axis.reverse()
+        } else {
+            y_axis
+        }
+    }
+
+    /// Create the secondary y-axis, if anything is drawn against it or its range has been set
+    fn create_secondary_axis(&self) -> Result<Option<axis::ContinuousAxis>> {
+        if self.secondary_representations.is_empty() && self.y2_range.is_none() {
+            return Ok(None);
+        }
 
-        Ok((x_axis, y_axis))
+        let y2_range = match &self.y2_range {
+            Some(range) => range.clone(),
+            None => {
+                let mut y_min = f64::INFINITY;
+                let mut y_max = f64::NEG_INFINITY;
+                for repr in &self.secondary_representations {
+                    let (this_y_min, this_y_max) = repr.range(1);
+                    y_min = y_min.min(this_y_min);
+                    y_max = y_max.max(this_y_max);
+                }
+                let (y_min, y_max) = utils::pad_range_to_zero(y_min, y_max);
+                let (y_min, y_max) = self.y2_range_policy.apply(
+                    y_min,
+                    y_max,
+                    self.y2_scale,
+                    self.y2_max_ticks,
+                    TickAlgorithm::Steps,
+                );
+                axis::Range::new(y_min, y_max)
+            }
+        };
+        if !y2_range.is_valid() {
+            return Err(format_err!(
+                "Invalid y2_range: {} >= {}. Please specify the y2_range manually.",
+                y2_range.lower,
+                y2_range.upper
+            ));
+        }
+        if !self.y2_scale.is_valid_range(y2_range.lower, y2_range.upper) {
+            return Err(format_err!(
+                "Invalid y2_range: {} to {} cannot be drawn with {:?} scale. Please specify the y2_range manually.",
+                y2_range.lower,
+                y2_range.upper,
+                self.y2_scale
+            ));
+        }
+
+        let y2_axis = axis::ContinuousAxis::scaled(
+            y2_range.lower,
+            y2_range.upper,
+            self.y2_max_ticks,
+            self.y2_scale,
+        )
+        .label(self.y2_label.clone().unwrap_or_default());
+        let y2_axis = match &self.y2_tick_format {
+            Some(format) => y2_axis.format(format.clone()),
+            None => y2_axis,
+        };
+        let y2_axis = match &self.y2_ticks {
+            Some(ticks) => y2_axis.explicit_ticks(ticks),
+            None => y2_axis,
+        };
+        let y2_axis = if self.y2_reversed {
+            y2_axis.reverse()
+        } else {
+            y2_axis
+        };
+        Ok(Some(y2_axis))
     }
 }
 
@@ -165,23 +798,55 @@ impl View for ContinuousView {
     fn to_svg(&self, face_width: f64, face_height: f64) -> Result<svg::node::element::Group> {
         let mut view_group = svg::node::element::Group::new();
 
-        let (x_axis, y_axis) = self.create_axes()?;
-
-        let (legend_x, mut legend_y) = (face_width - 100., -face_height);
-        if let Some(grid) = &self.grid {
-            view_group.append(svg_render::draw_grid(
-                GridType::Both(grid),
-                face_width,
-                face_height,
+        let (x_axes, y_axes) = self.create_axes()?;
+        let y2_axis = self.create_secondary_axis()?;
+        if y2_axis.is_some() && y_axes.len() > 1 {
+            return Err(format_err!(
+                "A secondary y-axis cannot be drawn beside a broken y-axis"
             ));
         }
+        let x_parts = axis::layout_segments(&x_axes, face_width, SEGMENT_GAP);
+        let y_parts = axis::layout_segments(&y_axes, face_height, SEGMENT_GAP);
 
-        // Then, based on those ranges, draw each repr as an SVG
-        for repr in &self.representations {
-            let repr_group = repr.to_svg(&x_axis, &y_axis, face_width, face_height);
-            view_group.append(repr_group);
+        // Then, based on those ranges, draw each repr as an SVG,
+        // once for each pair of segments of the axes
+        for (x_axis, &(x_offset, width)) in x_axes.iter().zip(&x_parts) {
+            for (y_axis, &(y_offset, height)) in y_axes.iter().zip(&y_parts) {
+                if let Some(grid) = &self.grid {
+                    let grid_group = svg_render::draw_grid(
+                        grid,
+                        &grid.continuous(x_axis, y_axis),
+                        width,
+                        height,
+                    );
+                    view_group.append(place(grid_group, x_offset, y_offset));
+                }
 
-            if let Some(legend_group) = repr.legend_svg() {
+                let mut face_group = svg::node::element::Group::new();
+                for &(repr, y_axis) in &self.layers(y_axis, y2_axis.as_ref()) {
+                    face_group.append(repr.to_svg(x_axis, y_axis, width, height));
+                }
+                let face_group = svg_render::clip_to_face(face_group, width, height);
+                view_group.append(place(face_group, x_offset, y_offset));
+            }
+        }
+
+        let layers = self.layers(&y_axes[0], y2_axis.as_ref());
+        if self.edge_markers && x_axes.len() == 1 && y_axes.len() == 1 {
+            for &(repr, y_axis) in &layers {
+                view_group.append(svg_render::draw_edge_markers(
+                    repr.points(),
+                    &x_axes[0],
+                    y_axis,
+                    face_width,
+                    face_height,
+                ));
+            }
+        }
+
+        let (legend_x, mut legend_y) = (face_width - 100., -face_height);
+        for &(repr, _) in &layers {
+            for legend_group in repr.legend_entries() {
                 view_group.append(legend_group.set(
                     "transform",
                     format!("translate({}, {})", legend_x, legend_y),
@@ -191,8 +856,25 @@ impl View for ContinuousView {
         }
 
         // Add in the axes
-        view_group.append(svg_render::draw_x_axis(&x_axis, face_width));
-        view_group.append(svg_render::draw_y_axis(&y_axis, face_height));
+        let x_segments: Vec<_> = x_axes
+            .iter()
+            .zip(&x_parts)
+            .map(|(a, &(offset, size))| (a, offset, size))
+            .collect();
+        let y_segments: Vec<_> = y_axes
+            .iter()
+            .zip(&y_parts)
+            .map(|(a, &(offset, size))| (a, offset, size))
+            .collect();
+        view_group.append(svg_render::draw_x_axis(&x_segments, face_width));
+        view_group.append(svg_render::draw_y_axis(&y_segments, face_height));
+        if let Some(y2_axis) = &y2_axis {
+            view_group.append(svg_render::draw_secondary_y_axis(
+                y2_axis,
+                face_width,
+                face_height,
+            ));
+        }
 
         Ok(view_group)
     }
@@ -201,68 +883,174 @@ impl View for ContinuousView {
     Create a text rendering of the view
     */
     fn to_text(&self, face_width: u32, face_height: u32) -> Result<String> {
-        let (x_axis, y_axis) = self.create_axes()?;
-
-        let (y_axis_string, longest_y_label_width) =
-            text_render::render_y_axis_strings(&y_axis, face_height);
+        self.render_text(
+            face_width,
+            face_height,
+            Colours::None,
+            |repr, x_axis, y_axis, face_width, face_height| {
+                let face = repr.to_text(x_axis, y_axis, face_width, face_height);
+                vec![(face, String::new())]
+            },
+        )
+    }
 
-        let (x_axis_string, start_offset) = text_render::render_x_axis_strings(&x_axis, face_width);
+    fn to_braille(&self, face_width: u32, face_height: u32) -> Result<String> {
+        self.render_text(
+            face_width,
+            face_height,
+            Colours::None,
+            |repr, x_axis, y_axis, face_width, face_height| {
+                let face = repr.to_braille(x_axis, y_axis, face_width, face_height);
+                vec![(face, String::new())]
+            },
+        )
+    }
 
-        let left_gutter_width = std::cmp::max(
-            longest_y_label_width as i32 + 3,
-            start_offset.wrapping_neg(),
-        ) as u32;
+    fn to_coloured_text(
+        &self,
+        face_width: u32,
+        face_height: u32,
+        braille: bool,
+        colours: Colours,
+    ) -> Result<String> {
+        self.render_text(
+            face_width,
+            face_height,
+            colours,
+            |repr, x_axis, y_axis, face_width, face_height| {
+                if braille {
+                    repr.braille_layers(x_axis, y_axis, face_width, face_height)
+                } else {
+                    repr.text_layers(x_axis, y_axis, face_width, face_height)
+                }
+            },
+        )
+    }
 
-        let view_width = face_width + 1 + left_gutter_width + 1;
-        let view_height = face_height + 4;
+    fn add_grid(&mut self, grid: Grid) {
+        self.grid = Some(grid)
+    }
 
-        let blank: Vec<String> = (0..view_height)
-            .map(|_| (0..view_width).map(|_| ' ').collect())
-            .collect();
-        let mut view_string = blank.join("\n");
+    fn grid(&self) -> &Option<Grid> {
+        &self.grid
+    }
 
-        for repr in &self.representations {
-            let face_string = repr.to_text(&x_axis, &y_axis, face_width, face_height);
-            view_string =
-                text_render::overlay(&view_string, &face_string, left_gutter_width as i32 + 1, 0);
-        }
+    fn has_secondary_axis(&self) -> bool {
+        !self.secondary_representations.is_empty() || self.y2_range.is_some()
+    }
+}
 
-        let view_string = text_render::overlay(
-            &view_string,
-            &y_axis_string,
-            left_gutter_width as i32 - 2 - longest_y_label_width,
-            0,
-        );
-        let view_string = text_render::overlay(
-            &view_string,
-            &x_axis_string,
-            left_gutter_width as i32,
-            face_height as i32,
-        );
+/// The gap left between the segments of a broken axis in SVG output
+const SEGMENT_GAP: f64 = 10.;
 
-        Ok(view_string)
+/// Check that the ranges of a broken axis are valid and in increasing order
+fn check_segments(name: &str, segments: &[axis::Range], scale: Scale) -> Result<()> {
+    if segments.is_empty() {
+        return Err(format_err!(
+            "Invalid {}: at least one range is needed.",
+            name
+        ));
+    }
+    for range in segments {
+        if !range.is_valid() {
+            return Err(format_err!(
+                "Invalid {}: {} >= {}.",
+                name,
+                range.lower,
+                range.upper
+            ));
+        }
+        if !scale.is_valid_range(range.lower, range.upper) {
+            return Err(format_err!(
+                "Invalid {}: {} to {} cannot be drawn with {:?} scale.",
+                name,
+                range.lower,
+                range.upper,
+                scale
+            ));
+        }
     }
+    for (previous, next) in segments.pairwise() {
+        if next.lower <= previous.upper {
+            return Err(format_err!(
+                "Invalid {}: {} to {} does not follow {} to {}. The ranges must be in increasing order without overlapping.",
+                name,
+                next.lower,
+                next.upper,
+                previous.lower,
+                previous.upper
+            ));
+        }
+    }
+    Ok(())
+}
 
-    fn add_grid(&mut self, grid: Grid) {
-        self.grid = Some(grid)
+/// Share the ticks of an axis between the segments of a broken axis by how much of
+/// the face each covers, with at least two for each so that it can be read
+fn segment_max_ticks(segments: &[axis::Range], scale: Scale, max_ticks: usize) -> Vec<usize> {
+    if segments.len() == 1 {
+        return vec![max_ticks];
     }
+    let spans: Vec<f64> = segments
+        .iter()
+        .map(|r| scale.transform(r.upper) - scale.transform(r.lower))
+        .collect();
+    let total: f64 = spans.iter().sum();
+    spans
+        .iter()
+        .map(|span| ((max_ticks as f64 * span / total).round() as usize).max(2))
+        .collect()
+}
 
-    fn grid(&self) -> &Option<Grid> {
-        &self.grid
+/// Lay out the segments of a broken axis along a text face, one cell apart,
+/// as each axis with the cell offset of its zero and its size in cells
+fn cell_segments(
+    axes: &[axis::ContinuousAxis],
+    face_cells: u32,
+) -> Vec<(&axis::ContinuousAxis, u32, u32)> {
+    axes.iter()
+        .zip(axis::layout_segments(axes, f64::from(face_cells), 1.))
+        .map(|(a, (offset, size))| {
+            let start = offset.round();
+            (a, start as u32, ((offset + size).round() - start) as u32)
+        })
+        .collect()
+}
+
+/// Move the part of the face drawn for one segment of a broken axis into place
+fn place(
+    group: svg::node::element::Group,
+    x_offset: f64,
+    y_offset: f64,
+) -> svg::node::element::Group {
+    if x_offset == 0. && y_offset == 0. {
+        group
+    } else {
+        group.set(
+            "transform",
+            format!("translate({}, {})", x_offset, -y_offset),
+        )
     }
 }
 
 /// A view with categorical entries along the x-axis and continuous values along the y-axis
-#[derive(Default)]
 pub struct CategoricalView {
     representations: Vec<Box<dyn CategoricalRepresentation>>,
     x_range: Option<Vec<String>>,
     y_range: Option<axis::Range>,
+    y_range_policy: RangePolicy,
+    y_segments: Option<Vec<axis::Range>>,
     x_label: Option<String>,
     y_label: Option<String>,
     grid: Option<Grid>,
 }
 
+impl Default for CategoricalView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl CategoricalView {
     /**
     Create an empty view
@@ -272,6 +1060,8 @@ impl CategoricalView {
             representations: vec![],
             x_range: None,
             y_range: None,
+            y_range_policy: RangePolicy::exact().padding(0.1),
+            y_segments: None,
             x_label: None,
             y_label: None,
             grid: None,
@@ -281,6 +1071,7 @@ impl CategoricalView {
     /**
     Add a representation to the view
     */
+    #[allow(clippy::should_implement_trait)]
     pub fn add<R: CategoricalRepresentation + 'static>(mut self, repr: R) -> Self {
         self.representations.push(Box::new(repr));
         self
@@ -302,6 +1093,32 @@ impl CategoricalView {
         self
     }
 
+    /**
+    Set how the y range is fitted to the data when it is not set explicitly
+
+    By default it is padded by 10% away from zero.
+    */
+    pub fn y_range_policy(mut self, policy: RangePolicy) -> Self {
+        self.y_range_policy = policy;
+        self
+    }
+
+    /**
+    Break the y-axis into segments covering each of the given ranges, in increasing order,
+    with a break marker between them
+
+    This takes the place of the y range.
+    */
+    pub fn y_segments(mut self, ranges: &[(f64, f64)]) -> Self {
+        self.y_segments = Some(
+            ranges
+                .iter()
+                .map(|&(lower, upper)| axis::Range::new(lower, upper))
+                .collect(),
+        );
+        self
+    }
+
     /**
     Set the label for the x-axis
     */
@@ -324,6 +1141,74 @@ impl CategoricalView {
         self
     }
 
+    /// Draw the axes around the face strings made for each representation
+    fn render_text(&self, face_width: u32, face_height: u32, colours: Colours) -> Result<String> {
+        let (x_axis, y_axes) = self.create_axes()?;
+        let y_segments = cell_segments(&y_axes, face_height);
+
+        let (y_axis_string, longest_y_label_width) =
+            text_render::render_y_axis_strings(&y_segments, face_height);
+
+        let x_axis_string = text_render::render_categorical_x_axis_strings(&x_axis, face_width);
+
+        let left_gutter_width = (longest_y_label_width + 3) as u32;
+
+        let view_width = face_width + 1 + left_gutter_width + 1;
+        let view_height = face_height + 4;
+
+        let blank: Vec<String> = (0..view_height)
+            .map(|_| (0..view_width).map(|_| ' ').collect())
+            .collect();
+        let mut view_string = blank.join("\n");
+
+        // Draw the face once for each segment of the y-axis, from its top-left cell
+        for &(y_axis, y_offset, height) in &y_segments {
+            let line = (face_height - y_offset - height) as i32;
+
+            // The grid is drawn first so that it sits under the data
+            if let Some(grid) = &self.grid {
+                let grid_string = text_render::render_face_grid(
+                    &grid.categorical(&x_axis, y_axis),
+                    face_width,
+                    height,
+                );
+                view_string = text_render::overlay(
+                    &view_string,
+                    &text_render::colourise(&grid_string, &grid.color, colours),
+                    left_gutter_width as i32 + 1,
+                    line,
+                );
+            }
+
+            for repr in &self.representations {
+                for (layer, colour) in repr.text_layers(&x_axis, y_axis, face_width, height) {
+                    let face_string = text_render::colourise(&layer, &colour, colours);
+                    view_string = text_render::overlay(
+                        &view_string,
+                        &face_string,
+                        left_gutter_width as i32 + 1,
+                        line,
+                    );
+                }
+            }
+        }
+
+        let view_string = text_render::overlay(
+            &view_string,
+            &y_axis_string,
+            left_gutter_width as i32 - 2 - longest_y_label_width,
+            0,
+        );
+        let view_string = text_render::overlay(
+            &view_string,
+            &x_axis_string,
+            left_gutter_width as i32,
+            face_height as i32,
+        );
+
+        Ok(view_string)
+    }
+
     fn default_x_ticks(&self) -> Vec<String> {
         let mut v = vec![];
         for repr in &self.representations {
@@ -344,22 +1229,32 @@ impl CategoricalView {
             y_min = y_min.min(this_y_min);
             y_max = y_max.max(this_y_max);
         }
-        let buffer = (y_max - y_min) / 10.;
-        let y_min = if y_min == 0.0 { y_min } else { y_min - buffer };
-        let y_max = y_max + buffer;
+        let (y_min, y_max) = utils::pad_range_to_zero(y_min, y_max);
+        let (y_min, y_max) =
+            self.y_range_policy
+                .apply(y_min, y_max, Scale::Linear, 6, TickAlgorithm::Steps);
         axis::Range::new(y_min, y_max)
     }
 
-    fn create_axes(&self) -> Result<(axis::CategoricalAxis, axis::ContinuousAxis)> {
+    /// Create the axes, with one y-axis for each segment of a broken axis
+    fn create_axes(&self) -> Result<(axis::CategoricalAxis, Vec<axis::ContinuousAxis>)> {
         let default_x_ticks = self.default_x_ticks();
         let x_range = self.x_range.as_ref().unwrap_or(&default_x_ticks);
 
-        let default_y_range = self.default_y_range();
-        let y_range = self.y_range.as_ref().unwrap_or(&default_y_range);
-
-        if !y_range.is_valid() {
-            return Err(format_err!("invalid y_range: {:?}", y_range));
-        }
+        let y_ranges = match &self.y_segments {
+            Some(segments) => {
+                check_segments("y_segments", segments, Scale::Linear)?;
+                segments.clone()
+            }
+            None => {
+                let default_y_range = self.default_y_range();
+                let y_range = self.y_range.as_ref().unwrap_or(&default_y_range);
+                if !y_range.is_valid() {
+                    return Err(format_err!("invalid y_range: {:?}", y_range));
+                }
+                vec![y_range.clone()]
+            }
+        };
 
         let default_x_label = "".to_string();
         let x_label: String = self.x_label.clone().unwrap_or(default_x_label);
@@ -368,9 +1263,15 @@ impl CategoricalView {
         let y_label: String = self.y_label.clone().unwrap_or(default_y_label);
 
         let x_axis = axis::CategoricalAxis::new(x_range).label(x_label);
-        let y_axis = axis::ContinuousAxis::new(y_range.lower, y_range.upper, 6).label(y_label);
+        let y_axes = y_ranges
+            .iter()
+            .zip(segment_max_ticks(&y_ranges, Scale::Linear, 6))
+            .map(|(r, max_ticks)| {
+                axis::ContinuousAxis::new(r.lower, r.upper, max_ticks).label(y_label.clone())
+            })
+            .collect();
 
-        Ok((x_axis, y_axis))
+        Ok((x_axis, y_axes))
     }
 }
 
@@ -378,31 +1279,57 @@ impl View for CategoricalView {
     fn to_svg(&self, face_width: f64, face_height: f64) -> Result<svg::node::element::Group> {
         let mut view_group = svg::node::element::Group::new();
 
-        let (x_axis, y_axis) = self.create_axes()?;
+        let (x_axis, y_axes) = self.create_axes()?;
+        let y_parts = axis::layout_segments(&y_axes, face_height, SEGMENT_GAP);
 
-        if let Some(grid) = &self.grid {
-            view_group.append(svg_render::draw_grid(
-                GridType::HorizontalOnly(grid),
-                face_width,
-                face_height,
-            ));
-        }
+        // Then, based on those ranges, draw each repr as an SVG,
+        // once for each segment of the y-axis
+        for (y_axis, &(y_offset, height)) in y_axes.iter().zip(&y_parts) {
+            if let Some(grid) = &self.grid {
+                let grid_group = svg_render::draw_grid(
+                    grid,
+                    &grid.categorical(&x_axis, y_axis),
+                    face_width,
+                    height,
+                );
+                view_group.append(place(grid_group, 0., y_offset));
+            }
 
-        // Then, based on those ranges, draw each repr as an SVG
-        for repr in &self.representations {
-            let repr_group = repr.to_svg(&x_axis, &y_axis, face_width, face_height);
-            view_group.append(repr_group);
+            let mut face_group = svg::node::element::Group::new();
+            for repr in &self.representations {
+                face_group.append(repr.to_svg(&x_axis, y_axis, face_width, height));
+            }
+            let face_group = svg_render::clip_to_face(face_group, face_width, height);
+            view_group.append(place(face_group, 0., y_offset));
         }
 
         // Add in the axes
+        let y_segments: Vec<_> = y_axes
+            .iter()
+            .zip(&y_parts)
+            .map(|(a, &(offset, size))| (a, offset, size))
+            .collect();
         view_group.append(svg_render::draw_categorical_x_axis(&x_axis, face_width));
-        view_group.append(svg_render::draw_y_axis(&y_axis, face_height));
+        view_group.append(svg_render::draw_y_axis(&y_segments, face_height));
 
         Ok(view_group)
     }
 
-    fn to_text(&self, _face_width: u32, _face_height: u32) -> Result<String> {
-        Ok("".into())
+    /**
+    Create a text rendering of the view
+    */
+    fn to_text(&self, face_width: u32, face_height: u32) -> Result<String> {
+        self.render_text(face_width, face_height, Colours::None)
+    }
+
+    fn to_coloured_text(
+        &self,
+        face_width: u32,
+        face_height: u32,
+        _braille: bool,
+        colours: Colours,
+    ) -> Result<String> {
+        self.render_text(face_width, face_height, colours)
     }
 
     fn add_grid(&mut self, grid: Grid) {
@@ -0,0 +1,53 @@
+use plotlib::page::Page;
+use plotlib::repr::{Area, ContinuousRepresentation};
+use plotlib::style::{AreaStyle, LineStyle};
+use plotlib::view::ContinuousView;
+
+#[test]
+fn test_area_range() {
+    let a = Area::new(vec![(1., 2.), (3., 5.)]);
+    // The baseline is part of the region
+    assert_eq!(a.range(0), (1., 3.));
+    assert_eq!(a.range(1), (0., 5.));
+
+    let a = Area::new(vec![(1., 2.), (3., 5.)]).baseline(4.);
+    assert_eq!(a.range(1), (2., 5.));
+
+    let a = Area::between(vec![(1., 2.), (3., 5.)], vec![(0., 1.), (4., 1.5)]);
+    assert_eq!(a.range(0), (0., 4.));
+    assert_eq!(a.range(1), (1., 5.));
+}
+
+#[test]
+fn test_area_svg() {
+    let a = Area::between(vec![(0., 2.), (4., 3.)], vec![(0., 1.), (4., 1.)])
+        .style(AreaStyle::new().fill("teal").opacity(0.5))
+        .line_style(LineStyle::new().colour("navy"))
+        .legend("Band".to_string());
+    let v = ContinuousView::new().add(a);
+    let svg = Page::single(&v).to_svg().unwrap().to_string();
+
+    let fill = svg
+        .lines()
+        .find(|line| line.contains("<path") && line.contains("fill=\"teal\""))
+        .expect("the area is filled");
+    assert!(fill.contains("fill-opacity=\"0.5\""));
+    assert!(fill.contains('z') || fill.contains('Z'));
+    // Both series are edged, and the legend has a swatch of the fill
+    assert_eq!(svg.matches("stroke=\"navy\"").count(), 3);
+    assert!(svg.contains("<rect fill=\"teal\""));
+    assert!(svg.lines().any(|line| line.trim() == "Band"));
+}
+
+#[test]
+fn test_area_text() {
+    let a = Area::new(vec![(0., 4.), (4., 4.)]);
+    let v = ContinuousView::new().add(a).x_range(0., 4.).y_range(0., 8.);
+    let text = Page::single(&v).dimensions(20, 8).to_text().unwrap();
+    let lines: Vec<&str> = text.lines().collect();
+
+    // The lower half of the face is shaded, up to the y-axis
+    assert!(!lines[0].contains('░'));
+    assert!(lines[7].ends_with(&format!("|{} ", "░".repeat(19))));
+    assert_eq!(text.matches('░').count(), 19 * 4);
+}
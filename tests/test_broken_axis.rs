@@ -0,0 +1,79 @@
+use plotlib::page::Page;
+use plotlib::repr::{BarChart, Plot};
+use plotlib::style::PointStyle;
+use plotlib::view::{CategoricalView, ContinuousView};
+
+#[test]
+fn test_broken_y_axis_text() {
+    let p = Plot::new(vec![(1., 1.), (2., 3.), (3., 98.)]).point_style(PointStyle::new());
+    let v = ContinuousView::new()
+        .add(p)
+        .x_range(0., 3.)
+        .y_segments(&[(0., 4.), (96., 100.)]);
+    let text = Page::single(&v).dimensions(30, 11).to_text().unwrap();
+    let lines: Vec<&str> = text.lines().collect();
+
+    // Each segment is ticked on its own, with a break marker between them
+    assert!(lines[0].contains("100-|"));
+    assert!(lines[5].contains("96-~"));
+    assert!(lines[11].contains("0+"));
+    // Points in both segments are drawn
+    assert_eq!(text.matches('●').count(), 3);
+}
+
+#[test]
+fn test_short_segment_text() {
+    let b1 = BarChart::new(2.).label("a");
+    let b2 = BarChart::new(100.).label("b");
+    let v = CategoricalView::new()
+        .add(b1)
+        .add(b2)
+        .y_segments(&[(0., 5.), (90., 110.)]);
+    let text = Page::single(&v).dimensions(30, 11).to_text().unwrap();
+    let lines: Vec<&str> = text.lines().collect();
+
+    // The short segment gets a share of the ticks it has room to label
+    assert!(lines[9].contains("5-|"));
+    assert!(lines[11].contains("0+"));
+    // so the small bar is drawn on the unlabelled row between them
+    assert!(lines[10].trim_start().starts_with("|   --------"));
+}
+
+#[test]
+fn test_broken_axes_svg() {
+    let p = Plot::new(vec![(0., 1.), (10., 98.)]).point_style(PointStyle::new());
+    let v = ContinuousView::new()
+        .add(p)
+        .x_segments(&[(0., 2.), (8., 10.)])
+        .y_segments(&[(0., 4.), (96., 100.)]);
+    let svg = Page::single(&v).to_svg().unwrap().to_string();
+    // The face is drawn and clipped once for each pair of segments
+    assert!(svg.contains("plotlib-face-3") && !svg.contains("plotlib-face-4"));
+
+    let b1 = BarChart::new(2.).label("a");
+    let b2 = BarChart::new(90.).label("b");
+    let v = CategoricalView::new()
+        .add(b1)
+        .add(b2)
+        .y_segments(&[(0., 5.), (85., 95.)]);
+    let svg = Page::single(&v).to_svg().unwrap().to_string();
+    assert!(svg.contains("plotlib-face-1") && !svg.contains("plotlib-face-2"));
+}
+
+#[test]
+fn test_invalid_segments() {
+    let p = Plot::new(vec![(0., 1.), (10., 98.)]);
+    let v = ContinuousView::new()
+        .add(p.clone())
+        .y_segments(&[(0., 50.), (40., 100.)]);
+    assert!(Page::single(&v).to_svg().is_err());
+    let v = ContinuousView::new().add(p.clone()).y_segments(&[]);
+    assert!(Page::single(&v).to_text().is_err());
+
+    // A secondary axis has nowhere to go beside a broken one
+    let v = ContinuousView::new()
+        .add(p.clone())
+        .add_secondary(p)
+        .y_segments(&[(0., 4.), (96., 100.)]);
+    assert!(Page::single(&v).to_svg().is_err());
+}
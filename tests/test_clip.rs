@@ -0,0 +1,43 @@
+use plotlib::page::Page;
+use plotlib::repr::Plot;
+use plotlib::style::PointStyle;
+use plotlib::view::ContinuousView;
+
+#[test]
+fn test_svg_clip_paths() {
+    let p = Plot::new(vec![(0., 0.), (10., 10.)]).point_style(PointStyle::new());
+    let v1 = ContinuousView::new().add(p.clone()).x_range(0., 5.);
+    let v2 = ContinuousView::new().add(p).y_range(0., 5.);
+    let svg = Page::empty()
+        .layout(1, 2)
+        .add_plot(&v1)
+        .add_plot(&v2)
+        .to_svg()
+        .unwrap()
+        .to_string();
+    // Each view's face has a clip path of its own
+    assert!(svg.contains("<clipPath id=\"plotlib-face-0\">"));
+    assert!(svg.contains("clip-path=\"url(#plotlib-face-1)\""));
+    assert!(!svg.contains("plotlib-face-2"));
+
+    // Ids start again for each document
+    let again = Page::single(&v1).to_svg().unwrap().to_string();
+    assert!(again.contains("plotlib-face-0") && !again.contains("plotlib-face-1"));
+}
+
+#[test]
+fn test_edge_markers() {
+    let p = Plot::new(vec![(1., 1.), (12., 5.), (5., -2.)]).point_style(PointStyle::new());
+    let v = ContinuousView::new()
+        .add(p)
+        .x_range(0., 10.)
+        .y_range(0., 10.);
+    let text = Page::single(&v).dimensions(30, 10).to_text().unwrap();
+    assert!(!text.contains('▶') && !text.contains('▼'));
+
+    let v = v.edge_markers();
+    let text = Page::single(&v).dimensions(30, 10).to_text().unwrap();
+    assert!(text.contains('▶') && text.contains('▼'));
+    let svg = Page::single(&v).to_svg().unwrap().to_string();
+    assert_eq!(svg.matches("fill=\"dimgrey\"").count(), 2);
+}
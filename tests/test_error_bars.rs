@@ -0,0 +1,65 @@
+use plotlib::page::Page;
+use plotlib::repr::{ContinuousRepresentation, Plot};
+use plotlib::style::{ErrorBarStyle, PointStyle};
+use plotlib::view::ContinuousView;
+
+#[test]
+fn test_error_bars_range() {
+    let p = Plot::new(vec![(1., 2.), (3., 5.)])
+        .x_errors(vec![0.5, 1.])
+        .y_errors_asymmetric(vec![(1.5, 0.), (0.25, 2.)]);
+    // The bars reach further than the points in every direction
+    assert_eq!(p.range(0), (0.5, 4.));
+    assert_eq!(p.range(1), (0.5, 7.));
+
+    let p = Plot::new(vec![(1., 2.), (3., 5.)]);
+    assert_eq!(p.range(0), (1., 3.));
+    assert_eq!(p.range(1), (2., 5.));
+}
+
+#[test]
+fn test_error_bars_svg() {
+    let p = Plot::new(vec![(1., 2.), (3., 5.)])
+        .y_errors(vec![1., 1.])
+        .point_style(PointStyle::new())
+        .error_style(ErrorBarStyle::new().colour("red").cap_width(0.));
+    let v = ContinuousView::new().add(p);
+    let svg = Page::single(&v).to_svg().unwrap().to_string();
+    let bars = svg
+        .lines()
+        .find(|line| line.contains("stroke=\"red\""))
+        .expect("error bars are drawn");
+    // One move and one line for each bar, without caps
+    assert_eq!(bars.matches('M').count(), 2);
+    assert_eq!(bars.matches('L').count(), 2);
+
+    let p = Plot::new(vec![(1., 2.), (3., 5.)])
+        .y_errors(vec![1., 1.])
+        .error_style(ErrorBarStyle::new().colour("red"));
+    let v = ContinuousView::new().add(p);
+    let svg = Page::single(&v).to_svg().unwrap().to_string();
+    let bars = svg
+        .lines()
+        .find(|line| line.contains("stroke=\"red\""))
+        .unwrap();
+    // Each bar gains a cap at both ends
+    assert_eq!(bars.matches('M').count(), 6);
+}
+
+#[test]
+fn test_error_bars_text() {
+    let p = Plot::new(vec![(0., 0.), (2., 2.), (4., 0.)])
+        .y_errors(vec![0., 2., 0.])
+        .point_style(PointStyle::new());
+    let v = ContinuousView::new()
+        .add(p)
+        .x_range(-1., 5.)
+        .y_range(-1., 5.);
+    let text = Page::single(&v).dimensions(30, 10).to_text().unwrap();
+
+    // The bar is capped at the top and bottom, with its point over it
+    assert_eq!(text.matches('┬').count(), 1);
+    assert_eq!(text.matches('┴').count(), 1);
+    assert_eq!(text.matches('│').count(), 4);
+    assert_eq!(text.matches('●').count(), 3);
+}
@@ -0,0 +1,31 @@
+use plotlib::format::TickFormat;
+use plotlib::page::Page;
+use plotlib::repr::Plot;
+use plotlib::view::ContinuousView;
+
+#[test]
+fn test_tick_formats() {
+    let p = Plot::new(vec![(0., 0.1), (2e6, 0.3)]);
+    let v = ContinuousView::new()
+        .add(p)
+        .x_tick_format(TickFormat::SI(1))
+        .y_tick_format(TickFormat::Percent(0));
+
+    let text = Page::single(&v).dimensions(40, 10).to_text().unwrap();
+    assert!(text.contains("1.2M") && text.contains("2M"));
+    // The gutter is wide enough for the widest label
+    assert!(text.lines().any(|l| l.starts_with("   30%-|")));
+
+    let svg = Page::single(&v).to_svg().unwrap().to_string();
+    assert!(svg.contains("400k") && svg.contains("30%"));
+}
+
+#[test]
+fn test_custom_format() {
+    let p = Plot::new(vec![(0., 1.), (3., 4.)]);
+    let v = ContinuousView::new()
+        .add(p)
+        .y_tick_format(TickFormat::custom(|v| format!("{} units", v)));
+    let text = Page::single(&v).dimensions(40, 10).to_text().unwrap();
+    assert!(text.contains("4 units-|"));
+}
@@ -0,0 +1,37 @@
+use plotlib::grid::Grid;
+use plotlib::page::Page;
+use plotlib::repr::{BarChart, Plot};
+use plotlib::style::PointStyle;
+use plotlib::view::{CategoricalView, ContinuousView, View};
+
+#[test]
+fn test_text_grid_under_data() {
+    let p = Plot::new(vec![(0., 0.), (4., 4.), (10., 10.)]).point_style(PointStyle::new());
+    let mut v = ContinuousView::new().add(p);
+    v.add_grid(Grid::at_ticks());
+
+    let text = Page::single(&v).dimensions(40, 20).to_text().unwrap();
+    let lines: Vec<_> = text.lines().collect();
+    // The point at (4, 4) is drawn over the crossing of the grid lines
+    let row = lines.iter().find(|l| l.contains(" 4-|")).unwrap();
+    assert!(row.contains('●') && row.contains('┄') && row.contains('┼'));
+    assert!(lines.iter().any(|l| l.contains('┆')));
+
+    // Without a grid, there are no grid glyphs
+    let v = ContinuousView::new().add(Plot::new(vec![(0., 0.), (10., 10.)]));
+    let text = Page::single(&v).dimensions(40, 20).to_text().unwrap();
+    assert!(!text.contains('┄'));
+}
+
+#[test]
+fn test_categorical_text_grid() {
+    let mut v = CategoricalView::new()
+        .add(BarChart::new(5.).label("a"))
+        .add(BarChart::new(2.).label("b"));
+    v.add_grid(Grid::new(3, 5));
+
+    let text = Page::single(&v).dimensions(30, 10).to_text().unwrap();
+    // Evenly spaced grids on categorical views only have horizontal lines
+    assert!(text.contains('┄'));
+    assert!(!text.contains('┆'));
+}
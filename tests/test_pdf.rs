@@ -0,0 +1,52 @@
+use plotlib::page::Page;
+use plotlib::report::Report;
+use plotlib::repr::{BarChart, BoxPlot, Plot};
+use plotlib::style::{LineStyle, PointMarker, PointStyle};
+use plotlib::view::{CategoricalView, ContinuousView};
+
+#[test]
+fn test_save_continuous_pdf() {
+    let p = Plot::new(vec![(0., 1.), (2., 1.5), (3., 1.2), (4., 1.1)])
+        .line_style(LineStyle::new().colour("burlywood"))
+        .point_style(PointStyle::new().marker(PointMarker::Circle));
+    let v = ContinuousView::new()
+        .add(p)
+        .x_label("Time (s)")
+        .y_label("Response");
+
+    Page::single(&v).save("/tmp/continuous.pdf").unwrap();
+    let bytes = std::fs::read("/tmp/continuous.pdf").unwrap();
+    assert!(bytes.starts_with(b"%PDF-"));
+    assert!(bytes.ends_with(b"%%EOF\n"));
+}
+
+#[test]
+fn test_categorical_pdf() {
+    let v = CategoricalView::new()
+        .add(BarChart::new(5.3).label("1"))
+        .add(BoxPlot::from_vec(vec![1., 2., 3., 6.]).label("2"));
+
+    let pdf = Page::single(&v).dimensions(300, 200).to_pdf().unwrap();
+    let contains = |needle: &[u8]| pdf.windows(needle.len()).any(|w| w == needle);
+    assert!(contains(b"/MediaBox [0 0 300 200]"));
+    assert!(contains(b"/BaseFont /Helvetica"));
+}
+
+#[test]
+fn test_save_report() {
+    let p = Plot::new(vec![(0., 1.), (2., 1.5), (3., 1.2)]);
+    let v = ContinuousView::new().add(p);
+    let c = CategoricalView::new().add(BarChart::new(5.3).label("1"));
+
+    Report::new()
+        .title("Results")
+        .add_page("Continuous", Page::single(&v))
+        .add_page("Categorical", Page::single(&c).dimensions(300, 200))
+        .page_numbers(true)
+        .table_of_contents(true)
+        .save("/tmp/report.pdf")
+        .unwrap();
+    let bytes = std::fs::read("/tmp/report.pdf").unwrap();
+    assert!(bytes.starts_with(b"%PDF-"));
+    assert!(bytes.windows(8).any(|w| w == b"/Count 3"));
+}
@@ -0,0 +1,36 @@
+use plotlib::page::Page;
+use plotlib::repr::{BarChart, Histogram, HistogramBins, Plot};
+use plotlib::style::{BoxStyle, LineStyle, PointStyle};
+use plotlib::view::{CategoricalView, ContinuousView};
+
+fn assert_is_png(path: &str) {
+    let bytes = std::fs::read(path).unwrap();
+    assert_eq!(&bytes[..8], b"\x89PNG\r\n\x1a\n");
+}
+
+#[test]
+fn test_save_continuous_png() {
+    let p = Plot::new(vec![(0., 1.), (2., 1.5), (3., 1.2), (4., 1.1)])
+        .line_style(LineStyle::new().colour("burlywood"))
+        .point_style(PointStyle::new());
+    let h = Histogram::from_slice(&[0.3, 0.5, 2.4, 3.6, 3.5], HistogramBins::Count(4))
+        .style(&BoxStyle::new().fill("steelblue"));
+    let v = ContinuousView::new().add(h).add(p).x_label("x");
+
+    Page::single(&v).save("/tmp/continuous.png").unwrap();
+    assert_is_png("/tmp/continuous.png");
+}
+
+#[test]
+fn test_save_categorical_png() {
+    let v = CategoricalView::new()
+        .add(BarChart::new(5.3).label("1"))
+        .add(BarChart::new(2.6).label("2"));
+
+    let png = Page::single(&v).dimensions(300, 200).to_png().unwrap();
+    // The width and height are stored big-endian in the header
+    assert_eq!(&png[16..24], &[0, 0, 1, 44, 0, 0, 0, 200]);
+
+    Page::single(&v).save("/tmp/categorical.png").unwrap();
+    assert_is_png("/tmp/categorical.png");
+}
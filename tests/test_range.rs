@@ -0,0 +1,64 @@
+use plotlib::page::Page;
+use plotlib::range::RangePolicy;
+use plotlib::repr::{BarChart, Plot};
+use plotlib::view::{CategoricalView, ContinuousView};
+
+#[test]
+fn test_continuous_range_policy() {
+    let p = Plot::new(vec![(1.3, 12.), (8.7, 27.)]);
+    let v = ContinuousView::new()
+        .add(p)
+        .y_range_policy(RangePolicy::exact().include_zero().nice());
+    let text = Page::single(&v).dimensions(50, 12).to_text().unwrap();
+    let lines: Vec<_> = text.lines().collect();
+    // The y-axis runs from zero up to the tick above the largest value
+    assert!(lines[0].trim_start().starts_with("30-|"));
+    assert!(lines[12].trim_start().starts_with("0+"));
+
+    // An explicit range ignores the policy
+    let v = v.y_range(10., 40.);
+    let text = Page::single(&v).dimensions(50, 12).to_text().unwrap();
+    assert!(text
+        .lines()
+        .nth(12)
+        .unwrap()
+        .trim_start()
+        .starts_with("10+"));
+}
+
+#[test]
+fn test_categorical_range_policy() {
+    let v = CategoricalView::new()
+        .add(BarChart::new(4.).label("a"))
+        .add(BarChart::new(10.).label("b"));
+    // By default the range is padded by 10% away from zero
+    let text = Page::single(&v).dimensions(30, 11).to_text().unwrap();
+    assert!(text.lines().nth(11).unwrap().trim_start().starts_with("0+"));
+    assert!(!text.lines().next().unwrap().contains("10-|"));
+
+    let v = v.y_range_policy(RangePolicy::exact());
+    let text = Page::single(&v).dimensions(30, 10).to_text().unwrap();
+    assert!(text
+        .lines()
+        .next()
+        .unwrap()
+        .trim_start()
+        .starts_with("10-|"));
+}
+
+#[test]
+fn test_small_range_policy() {
+    let p = Plot::new(vec![(0., 1e-7), (1., 5e-7)]);
+    let v = ContinuousView::new()
+        .add(p)
+        .y_range_policy(RangePolicy::exact().nice());
+    assert!(Page::single(&v).dimensions(50, 12).to_text().is_ok());
+}
+
+#[test]
+fn test_categorical_default_matches_new() {
+    let render = |v: &CategoricalView| Page::single(v).dimensions(30, 11).to_text().unwrap();
+    let a = CategoricalView::new().add(BarChart::new(10.).label("a"));
+    let b = CategoricalView::default().add(BarChart::new(10.).label("a"));
+    assert_eq!(render(&a), render(&b));
+}
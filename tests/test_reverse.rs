@@ -0,0 +1,33 @@
+use plotlib::page::Page;
+use plotlib::repr::{Histogram, HistogramBins, Plot};
+use plotlib::style::PointStyle;
+use plotlib::view::ContinuousView;
+
+#[test]
+fn test_reversed_text() {
+    let p = Plot::new(vec![(0., 0.), (10., 10.)]).point_style(PointStyle::new());
+    let v = ContinuousView::new().add(p).x_reverse().y_reverse();
+    let text = Page::single(&v).dimensions(30, 10).to_text().unwrap();
+    let lines: Vec<&str> = text.lines().collect();
+
+    // The minimum of the y-axis is at the top, and of the x-axis at the right
+    assert!(lines[0].trim_start().starts_with("0-|"));
+    assert!(lines[10].trim_start().starts_with("10+"));
+    let x_labels = lines[12].split_whitespace().collect::<Vec<_>>();
+    assert_eq!(x_labels.first(), Some(&"10"));
+    assert_eq!(x_labels.last(), Some(&"0"));
+
+    // Both points lie in the corners they were moved to
+    assert!(lines[0].trim_end().ends_with('●'));
+    assert_eq!(text.matches('●').count(), 1);
+}
+
+#[test]
+fn test_reversed_svg() {
+    let h = Histogram::from_slice(&[1., 2., 2., 3.], HistogramBins::Count(2));
+    let v = ContinuousView::new().add(h).y_reverse();
+    let svg = Page::single(&v).to_svg().unwrap().to_string();
+    // Both bars hang from the top of the face rather than standing on the bottom
+    assert!(svg.contains("width=\"240\" x=\"0\" y=\"-340\""));
+    assert!(svg.contains("width=\"240\" x=\"240\" y=\"-340\""));
+}
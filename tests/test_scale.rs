@@ -0,0 +1,31 @@
+use plotlib::page::Page;
+use plotlib::repr::Plot;
+use plotlib::scale::Scale;
+use plotlib::view::ContinuousView;
+
+#[test]
+fn test_log_scale() {
+    let p = Plot::new(vec![(1., 0.1), (10., 3.), (100., 250.)]);
+    let v = ContinuousView::new()
+        .add(p)
+        .x_scale(Scale::Log10)
+        .y_scale(Scale::SymLog(1.));
+
+    let text = Page::single(&v).dimensions(40, 10).to_text().unwrap();
+    let labels = text.lines().nth(12).unwrap();
+    assert!(labels.contains("1") && labels.contains("10") && labels.contains("100"));
+    assert!(Page::single(&v).to_svg().is_ok());
+}
+
+#[test]
+fn test_invalid_log_range() {
+    let p = Plot::new(vec![(0., 1.), (10., 3.)]);
+    let v = ContinuousView::new().add(p).x_scale(Scale::Log10);
+    assert!(Page::single(&v).to_text().is_err());
+
+    let v = ContinuousView::new()
+        .add(Plot::new(vec![(0., 1.), (10., 3.)]))
+        .x_scale(Scale::Log10)
+        .x_range(0.1, 10.);
+    assert!(Page::single(&v).to_text().is_ok());
+}
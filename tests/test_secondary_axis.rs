@@ -0,0 +1,56 @@
+use plotlib::page::Page;
+use plotlib::repr::Plot;
+use plotlib::style::PointStyle;
+use plotlib::view::ContinuousView;
+
+#[test]
+fn test_secondary_axis_text() {
+    let p1 = Plot::new(vec![(0., 1.), (4., 2.)]).point_style(PointStyle::new());
+    let p2 = Plot::new(vec![(0., 100.), (4., 900.)]).point_style(PointStyle::new());
+    let v = ContinuousView::new()
+        .add(p1)
+        .add_secondary(p2)
+        .y2_label("Rain");
+    let text = Page::single(&v).dimensions(40, 12).to_text().unwrap();
+    let lines: Vec<&str> = text.lines().collect();
+
+    // The secondary tick labels are to the right of the face
+    let top = lines.iter().find(|l| l.contains("900")).unwrap();
+    assert!(top.find("900").unwrap() > top.find('●').unwrap());
+    assert!(text.contains("+-100"));
+    assert!(lines.iter().any(|l| l.trim_end().ends_with('R')));
+}
+
+#[test]
+fn test_secondary_axis_svg() {
+    let p1 = Plot::new(vec![(0., 1.), (4., 2.)]).point_style(PointStyle::new());
+    let v = ContinuousView::new().add(p1.clone());
+    let svg = Page::single(&v).to_svg().unwrap().to_string();
+    // Only the right-hand tick labels are anchored at their start
+    assert!(!svg.contains("text-anchor=\"start\""));
+
+    let p2 = Plot::new(vec![(0., 100.), (4., 900.)]).point_style(PointStyle::new());
+    let v = v.add_secondary(p2).y2_label("Rain");
+    let svg = Page::single(&v).to_svg().unwrap().to_string();
+    assert_eq!(svg.matches("text-anchor=\"start\"").count(), 5);
+    assert!(svg.contains("Rain"));
+}
+
+#[test]
+fn test_secondary_axis_range() {
+    let p1 = Plot::new(vec![(0., 1.), (4., 2.)]);
+    let v = ContinuousView::new().add(p1).y2_range(5., 5.);
+    assert!(Page::single(&v).to_svg().is_err());
+}
+
+#[test]
+fn test_secondary_axis_default_view() {
+    let p1 = Plot::new(vec![(0., 1.), (4., 2.)]).point_style(PointStyle::new());
+    let p2 = Plot::new(vec![(0., 100.), (4., 900.)]).point_style(PointStyle::new());
+    let render = |v: ContinuousView| Page::single(&v).to_svg().unwrap().to_string();
+    let a = ContinuousView::new()
+        .add(p1.clone())
+        .add_secondary(p2.clone());
+    let b = ContinuousView::default().add(p1).add_secondary(p2);
+    assert_eq!(render(a), render(b));
+}
@@ -0,0 +1,77 @@
+use plotlib::page::Page;
+use plotlib::repr::{ContinuousRepresentation, Plot, StackedArea};
+use plotlib::style::{AreaStyle, LineStyle, PALETTE};
+use plotlib::view::ContinuousView;
+
+#[test]
+fn test_stacked_area_range() {
+    let s = StackedArea::new(vec![0., 1., 2.])
+        .add_layer("a", vec![1., 2., 3.])
+        .add_layer("b", vec![4., 1., 1.])
+        .add_layer("c", vec![1., 1.]);
+    assert_eq!(s.range(0), (0., 2.));
+    // The top of the stack rather than the largest single value, with missing values as zero
+    assert_eq!(s.range(1), (0., 6.));
+
+    let s = s.normalised();
+    assert_eq!(s.range(1), (0., 100.));
+
+    let s = StackedArea::new(vec![0., 1.])
+        .add_layer("a", vec![0., 0.])
+        .normalised();
+    assert_eq!(s.range(1), (0., 0.));
+}
+
+#[test]
+fn test_stacked_area_svg() {
+    let s = StackedArea::new(vec![0., 1., 2.])
+        .add_layer("first", vec![1., 2., 3.])
+        .add_styled_layer("second", vec![2., 2., 2.], AreaStyle::new().opacity(0.5))
+        .add_styled_layer("third", vec![1., 1., 1.], AreaStyle::new().fill("black"))
+        .line_style(LineStyle::new().colour("white"));
+    let p = Plot::new(vec![(0., 1.), (2., 5.)])
+        .line_style(LineStyle::new())
+        .legend("trend".to_string());
+    let v = ContinuousView::new().add(s).add(p);
+    let svg = Page::single(&v).to_svg().unwrap().to_string();
+
+    // Each layer takes the next colour of the palette unless its fill is set
+    let fills: Vec<&str> = svg
+        .lines()
+        .filter(|line| line.contains("<path") && line.contains("fill-opacity"))
+        .collect();
+    assert_eq!(fills.len(), 3);
+    assert!(fills[0].contains(&format!("fill=\"{}\"", PALETTE[0])));
+    assert!(fills[1].contains(&format!("fill=\"{}\"", PALETTE[1])));
+    assert!(fills[1].contains("fill-opacity=\"0.5\""));
+    assert!(fills[2].contains("fill=\"black\""));
+    assert_eq!(svg.matches("stroke=\"white\"").count(), 3 + 3);
+
+    // Every layer has a legend entry, from the top of the stack down, followed by the plot's
+    let legend: Vec<&str> = svg
+        .lines()
+        .map(str::trim)
+        .filter(|line| ["first", "second", "third", "trend"].contains(line))
+        .collect();
+    assert_eq!(legend, vec!["third", "second", "first", "trend"]);
+    for y in &[0, 18, 36, 54] {
+        let translate = format!("translate(380, {})", y - 340);
+        assert_eq!(svg.matches(&translate).count(), 1, "{}", translate);
+    }
+}
+
+#[test]
+fn test_stacked_area_text() {
+    let s = StackedArea::new(vec![0., 4.])
+        .add_layer("a", vec![2., 2.])
+        .add_layer("b", vec![2., 2.]);
+    let v = ContinuousView::new().add(s).y_range(0., 8.);
+    let text = Page::single(&v).dimensions(20, 8).to_text().unwrap();
+
+    // The layers are shaded differently, one above the other
+    assert_eq!(text.matches('░').count(), 19 * 2);
+    assert_eq!(text.matches('▒').count(), 19 * 2);
+    let lines: Vec<&str> = text.lines().collect();
+    assert!(lines[4].contains('▒'));
+    assert!(lines[7].contains('░'));
+}
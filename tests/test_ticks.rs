@@ -0,0 +1,72 @@
+use plotlib::page::Page;
+use plotlib::repr::Plot;
+use plotlib::ticks::TickAlgorithm;
+use plotlib::view::ContinuousView;
+
+#[test]
+fn test_explicit_ticks() {
+    let p = Plot::new(vec![(0., 10.), (12., 95.)]);
+    let v = ContinuousView::new()
+        .add(p)
+        .x_tick_labels(&[(0., "Q1"), (3., "Q2"), (6., "Q3"), (9., "Q4"), (15., "Q5")])
+        .y_ticks(&[0., 50., 90.]);
+
+    let text = Page::single(&v).dimensions(48, 10).to_text().unwrap();
+    let lines: Vec<_> = text.lines().collect();
+    assert!(lines[12].contains("Q1") && lines[12].contains("Q4"));
+    // Ticks outside the range are dropped
+    assert!(!text.contains("Q5"));
+    assert_eq!(lines[11].matches('|').count(), 4);
+    assert!(text.contains("90-|") && text.contains("50-|"));
+    assert!(!text.contains("20-|"));
+
+    let svg = Page::single(&v).to_svg().unwrap().to_string();
+    assert!(svg.contains("Q3"));
+
+    // No ticks at all still renders
+    let v = ContinuousView::new()
+        .add(Plot::new(vec![(0., 10.), (12., 95.)]))
+        .x_ticks(&[])
+        .y_ticks(&[100.]);
+    assert!(Page::single(&v).to_text().is_ok());
+    assert!(Page::single(&v).to_svg().is_ok());
+}
+
+#[test]
+fn test_minor_ticks() {
+    let p = Plot::new(vec![(0., 0.), (10., 10.)]);
+    let v = ContinuousView::new()
+        .add(p)
+        .x_minor_ticks(2)
+        .y_minor_ticks(2);
+
+    let text = Page::single(&v).dimensions(40, 20).to_text().unwrap();
+    let lines: Vec<_> = text.lines().collect();
+    // Minor ticks sit halfway between the major ones, with no label
+    assert_eq!(lines[21].matches('|').count(), 6);
+    assert_eq!(lines[21].matches('\'').count(), 5);
+    assert_eq!(lines[2].trim(), "'|");
+
+    let svg = Page::single(&v).to_svg().unwrap().to_string();
+    assert_eq!(svg.matches("y2=\"5\"").count(), 5);
+}
+
+#[test]
+fn test_extended_ticks() {
+    let p = Plot::new(vec![(0.13, 3.2), (8.7, 27.)]);
+    let v = ContinuousView::new()
+        .add(p)
+        .x_range(0.13, 8.7)
+        .y_range(3.2, 27.)
+        .y_tick_algorithm(TickAlgorithm::Extended { expand: false });
+    let text = Page::single(&v).dimensions(50, 12).to_text().unwrap();
+    assert!(text.contains("25-|") && text.contains(" 5-|"));
+    assert!(!text.contains("23.2"));
+
+    // Expanding the range starts and ends the axis on a tick
+    let v = v.x_tick_algorithm(TickAlgorithm::Extended { expand: true });
+    let text = Page::single(&v).dimensions(50, 12).to_text().unwrap();
+    let lines: Vec<_> = text.lines().collect();
+    assert!(lines[14].trim_start().starts_with('0'));
+    assert!(lines[14].trim_end().ends_with("10"));
+}
@@ -0,0 +1,23 @@
+use plotlib::page::Page;
+use plotlib::repr::Plot;
+use plotlib::time::timestamp;
+use plotlib::view::ContinuousView;
+
+#[test]
+fn test_time_axis_text() {
+    let start = timestamp(2020, 3, 14, 0, 0, 0);
+    let p = Plot::new(vec![(start, 1.), (start + 86400., 3.)]);
+    let v = ContinuousView::new().add(p).x_time();
+
+    let text = Page::single(&v).dimensions(40, 10).to_text().unwrap();
+    let lines: Vec<_> = text.lines().collect();
+    assert!(lines[12].trim_start().starts_with("00:00"));
+    assert!(lines[12].contains("12:00"));
+    // The first tick is at the start of the axis
+    assert_eq!(lines[10].find('+'), lines[11].find('|'));
+
+    let v = v.x_time_format("%d %b");
+    let text = Page::single(&v).dimensions(40, 10).to_text().unwrap();
+    assert!(text.contains("14 Mar") && text.contains("15 Mar"));
+    assert!(Page::single(&v).to_svg().is_ok());
+}